@@ -0,0 +1,233 @@
+use crate::commands;
+use crate::error::AppError;
+use crate::health;
+use crate::logging;
+use crate::models::{LogLevel, ProcessBinding, ServiceConfig, ServiceStatus};
+use crate::state::AppState;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How often `/proc` is scanned for bound processes.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub fn validate(binding: &ProcessBinding) -> Result<(), AppError> {
+    if !cfg!(target_os = "linux") {
+        return Err(AppError::Config(
+            "Binding to a process is only supported on Linux".into(),
+        ));
+    }
+    match binding {
+        ProcessBinding::Pid { pid } if *pid == 0 => {
+            Err(AppError::Config("PID must not be 0".into()))
+        }
+        ProcessBinding::Pidfile { path } if path.trim().is_empty() => {
+            Err(AppError::Config("Pidfile path must not be empty".into()))
+        }
+        ProcessBinding::Name { pattern } if pattern.trim().is_empty() => Err(AppError::Config(
+            "Process name pattern must not be empty".into(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// PID of a process matching the binding, if one is running.
+pub fn find(binding: &ProcessBinding) -> Option<u32> {
+    match binding {
+        ProcessBinding::Pid { pid } => proc::exists(*pid).then_some(*pid),
+        ProcessBinding::Pidfile { path } => {
+            let pid = parse_pidfile(&std::fs::read_to_string(path).ok()?)?;
+            proc::exists(pid).then_some(pid)
+        }
+        ProcessBinding::Name { pattern } => proc::find_by_name(pattern.trim()),
+    }
+}
+
+fn parse_pidfile(contents: &str) -> Option<u32> {
+    contents
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+        .filter(|pid| *pid != 0)
+}
+
+#[cfg(target_os = "linux")]
+mod proc {
+    use crate::network;
+    use std::fs;
+
+    pub fn exists(pid: u32) -> bool {
+        // Zombies keep their /proc entry until reaped
+        match fs::read_to_string(format!("/proc/{}/stat", pid)) {
+            Ok(stat) => !stat
+                .rsplit_once(')')
+                .is_some_and(|(_, rest)| rest.trim_start().starts_with('Z')),
+            Err(_) => false,
+        }
+    }
+
+    pub fn find_by_name(pattern: &str) -> Option<u32> {
+        let own = std::process::id();
+        fs::read_dir("/proc")
+            .ok()?
+            .filter_map(|e| e.ok()?.file_name().to_str()?.parse::<u32>().ok())
+            .filter(|pid| *pid != own)
+            .find(|pid| matches_name(*pid, pattern) && exists(*pid))
+    }
+
+    fn matches_name(pid: u32, pattern: &str) -> bool {
+        let comm = fs::read_to_string(format!("/proc/{}/comm", pid)).unwrap_or_default();
+        if network::glob_match(pattern, comm.trim_end()) {
+            return true;
+        }
+        let cmdline = fs::read(format!("/proc/{}/cmdline", pid)).unwrap_or_default();
+        let cmdline = String::from_utf8_lossy(&cmdline);
+        let cmdline = cmdline.trim_end_matches('\0').replace('\0', " ");
+        !cmdline.is_empty() && network::glob_match(pattern, &cmdline)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod proc {
+    pub fn exists(_pid: u32) -> bool {
+        false
+    }
+
+    pub fn find_by_name(_pattern: &str) -> Option<u32> {
+        None
+    }
+}
+
+/// Poll bound processes in the background until the app shuts down.
+pub fn spawn_watcher(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || loop {
+        thread::sleep(POLL_INTERVAL);
+        let state = app.state::<AppState>();
+        if *state.shut_down.lock().unwrap() {
+            break;
+        }
+        check_bound_services(&app, &state);
+    });
+}
+
+fn check_bound_services(app: &AppHandle, state: &AppState) {
+    let (candidates, hostname) = {
+        let config = state.config.lock().unwrap();
+        let statuses = state.statuses.lock().unwrap();
+        let candidates: Vec<(ServiceConfig, ServiceStatus)> = config
+            .services
+            .iter()
+            .filter(|s| s.bound_process.is_some())
+            .filter_map(|s| {
+                let status = statuses.get(&s.id).copied()?;
+                status.is_active().then(|| (s.clone(), status))
+            })
+            .collect();
+        (candidates, config.effective_hostname().to_string())
+    };
+
+    let mut changed = false;
+    for (svc, status) in &candidates {
+        let Some(binding) = &svc.bound_process else {
+            continue;
+        };
+        match (status, find(binding)) {
+            // Still waiting for its health check, which publishes it
+            (ServiceStatus::Waiting, Some(_)) if health::must_wait(state, svc) => {}
+            (ServiceStatus::Waiting, Some(pid)) => {
+                logging::append_log(
+                    app,
+                    state,
+                    LogLevel::Info,
+                    format!(
+                        "Bound process for service '{}' found (pid {})",
+                        svc.name, pid
+                    ),
+                    Some(svc.id.clone()),
+                );
+                commands::try_register_service(app, state, svc, &hostname);
+                changed = true;
+            }
+            (ServiceStatus::Running | ServiceStatus::Unhealthy, None) => {
+                let _ = commands::unregister_on_daemon(state, svc, false);
+                {
+                    let mut statuses = state.statuses.lock().unwrap();
+                    statuses.insert(svc.id.clone(), ServiceStatus::Waiting);
+                }
+                logging::append_log(
+                    app,
+                    state,
+                    LogLevel::Warn,
+                    format!(
+                        "Bound process for service '{}' is gone, advertisement withdrawn",
+                        svc.name
+                    ),
+                    Some(svc.id.clone()),
+                );
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+
+    if changed {
+        if let Ok(views) = commands::build_views(state) {
+            let _ = app.emit("services-changed", &views);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pidfile_reads_first_token() {
+        assert_eq!(parse_pidfile("1234\n"), Some(1234));
+        assert_eq!(parse_pidfile("  42 extra"), Some(42));
+        assert_eq!(parse_pidfile("0"), None);
+        assert_eq!(parse_pidfile("abc"), None);
+        assert_eq!(parse_pidfile(""), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn find_sees_running_process() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        assert_eq!(find(&ProcessBinding::Pid { pid }), Some(pid));
+        // The command line can read empty for a moment right after exec
+        let by_name = ProcessBinding::Name {
+            pattern: "sleep 30".into(),
+        };
+        let mut found = None;
+        for _ in 0..20 {
+            found = find(&by_name);
+            if found.is_some() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        assert!(found.is_some());
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert_eq!(find(&ProcessBinding::Pid { pid }), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn validate_rejects_empty_binding() {
+        assert!(validate(&ProcessBinding::Pid { pid: 1 }).is_ok());
+        assert!(validate(&ProcessBinding::Pid { pid: 0 }).is_err());
+        assert!(validate(&ProcessBinding::Name {
+            pattern: " ".into()
+        })
+        .is_err());
+    }
+}
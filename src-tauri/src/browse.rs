@@ -0,0 +1,516 @@
+use crate::error::AppError;
+use crate::logging;
+use crate::mdns;
+use crate::models::{BrowseEvent, DiscoveredService, LogLevel, ServiceTypeSummary};
+use crate::state::{AppState, ResolveWaiter, TypeSighting};
+use crate::txt;
+use crate::watch;
+use chrono::Utc;
+use mdns_sd::{ResolvedService, ServiceDaemon, ServiceEvent};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How long `resolve_instance` waits for a remote instance to resolve.
+const RESOLVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// DNS-SD service type enumeration (RFC 6763 section 9). Browsing it yields
+/// the service types announced on the network instead of instances.
+const META_QUERY: &str = "_services._dns-sd._udp.local.";
+
+/// Browsing runs on its own daemon so that it does not replace or stop the
+/// queries that self-verification issues on the publishing daemons.
+pub fn browse_daemon(state: &AppState) -> Result<ServiceDaemon, AppError> {
+    let mut browser = state.browser.lock().unwrap();
+    if let Some(daemon) = browser.daemon.as_ref() {
+        return Ok(daemon.clone());
+    }
+    let daemon = mdns::create_daemon()?;
+    browser.daemon = Some(daemon.clone());
+    Ok(daemon)
+}
+
+/// Check a type entered to browse for and return it fully qualified, with
+/// any subtype kept.
+pub fn validate_type(service_type: &str) -> Result<String, AppError> {
+    let service_type = service_type.trim();
+    if service_type.is_empty() || !service_type.starts_with('_') {
+        return Err(AppError::Config(format!(
+            "Invalid service type '{}'",
+            service_type
+        )));
+    }
+    mdns::to_browse_type(service_type)
+}
+
+/// Query `ty_domain` on the browse daemon unless a query is already running.
+/// Events are handled on a background thread until the query is stopped.
+pub fn query(app: &AppHandle, state: &AppState, ty_domain: &str) -> Result<(), AppError> {
+    {
+        let mut browser = state.browser.lock().unwrap();
+        if !browser.queries.insert(ty_domain.to_string()) {
+            return Ok(());
+        }
+    }
+
+    let receiver = match browse_daemon(state).and_then(|daemon| {
+        daemon
+            .browse(ty_domain)
+            .map_err(|e| AppError::Mdns(e.to_string()))
+    }) {
+        Ok(receiver) => receiver,
+        Err(e) => {
+            state.browser.lock().unwrap().queries.remove(ty_domain);
+            return Err(e);
+        }
+    };
+
+    let app = app.clone();
+    let ty = ty_domain.to_string();
+    thread::spawn(move || {
+        while let Ok(event) = receiver.recv() {
+            if let ServiceEvent::SearchStopped(_) = event {
+                break;
+            }
+            handle_event(&app, &ty, event);
+        }
+
+        let state = app.state::<AppState>();
+        let was_shown = {
+            let mut browser = state.browser.lock().unwrap();
+            browser.queries.remove(&ty);
+            browser.resolved.remove(&ty);
+            browser.shown.remove(&ty)
+        };
+        if was_shown {
+            let _ = app.emit(
+                "browse-event",
+                &BrowseEvent::Stopped {
+                    service_type: ty.clone(),
+                },
+            );
+        }
+    });
+    Ok(())
+}
+
+fn handle_event(app: &AppHandle, ty: &str, event: ServiceEvent) {
+    let state = app.state::<AppState>();
+
+    if ty == META_QUERY {
+        // Found and removed "instances" of the meta query are service types
+        if let ServiceEvent::ServiceFound(_, found) = event {
+            {
+                let mut browser = state.browser.lock().unwrap();
+                let Some(types) = browser.types.as_mut() else {
+                    return;
+                };
+                let now = Utc::now();
+                types
+                    .entry(found.clone())
+                    .and_modify(|t| t.last_seen = now)
+                    .or_insert_with(|| TypeSighting {
+                        first_seen: now,
+                        last_seen: now,
+                        instances: HashSet::new(),
+                    });
+            }
+            if let Err(e) = query(app, &state, &found) {
+                logging::append_log(
+                    app,
+                    &state,
+                    LogLevel::Warn,
+                    format!("Failed to browse for {}: {}", found, e),
+                    None,
+                );
+            }
+            emit_types(app, &state);
+        }
+        return;
+    }
+
+    // Our host aliases ride on placeholder instances nobody can connect to
+    let fullname = match &event {
+        ServiceEvent::ServiceFound(_, fullname) | ServiceEvent::ServiceRemoved(_, fullname) => {
+            Some(fullname.as_str())
+        }
+        ServiceEvent::ServiceResolved(resolved) => Some(resolved.fullname.as_str()),
+        _ => None,
+    };
+    if fullname.is_some_and(|f| is_alias_placeholder(&state, f)) {
+        return;
+    }
+
+    match event {
+        ServiceEvent::ServiceFound(_, fullname) => {
+            record_instance(&state, ty, Some(&fullname), None);
+            emit_types(app, &state);
+        }
+        ServiceEvent::ServiceResolved(resolved) => {
+            let service = discovered(app, &resolved);
+            let shown = {
+                let mut browser = state.browser.lock().unwrap();
+                browser
+                    .resolved
+                    .entry(ty.to_string())
+                    .or_default()
+                    .insert(service.fullname.clone(), service.clone());
+                let key = service.fullname.to_ascii_lowercase();
+                browser.resolving.retain(|w| {
+                    if w.fullname != key {
+                        return true;
+                    }
+                    let _ = w.sender.send(service.clone());
+                    false
+                });
+                browser.shown.contains(ty)
+            };
+            record_instance(&state, ty, Some(&service.fullname), None);
+            emit_types(app, &state);
+            watch::observe_resolved(app, &state, &service);
+            if shown {
+                let _ = app.emit("browse-event", &BrowseEvent::Resolved { service });
+            }
+        }
+        ServiceEvent::ServiceRemoved(service_type, fullname) => {
+            let shown = {
+                let mut browser = state.browser.lock().unwrap();
+                if let Some(resolved) = browser.resolved.get_mut(ty) {
+                    resolved.remove(&fullname);
+                }
+                browser.shown.contains(ty)
+            };
+            record_instance(&state, ty, None, Some(&fullname));
+            emit_types(app, &state);
+            watch::observe_removed(app, &state, &fullname);
+            if shown {
+                let _ = app.emit(
+                    "browse-event",
+                    &BrowseEvent::Removed {
+                        service_type,
+                        fullname,
+                    },
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Track an instance appearing or disappearing under `ty` while enumeration
+/// is on.
+fn record_instance(state: &AppState, ty: &str, added: Option<&str>, removed: Option<&str>) {
+    let mut browser = state.browser.lock().unwrap();
+    let Some(types) = browser.types.as_mut() else {
+        return;
+    };
+    let now = Utc::now();
+    let sighting = types.entry(ty.to_string()).or_insert_with(|| TypeSighting {
+        first_seen: now,
+        last_seen: now,
+        instances: HashSet::new(),
+    });
+    if let Some(fullname) = added {
+        sighting.last_seen = now;
+        sighting.instances.insert(fullname.to_string());
+    }
+    if let Some(fullname) = removed {
+        sighting.instances.remove(fullname);
+    }
+}
+
+fn summaries(types: &BTreeMap<String, TypeSighting>) -> Vec<ServiceTypeSummary> {
+    types
+        .iter()
+        .map(|(service_type, sighting)| ServiceTypeSummary {
+            service_type: service_type.clone(),
+            instances: sighting.instances.len(),
+            first_seen: sighting.first_seen,
+            last_seen: sighting.last_seen,
+        })
+        .collect()
+}
+
+fn emit_types(app: &AppHandle, state: &AppState) {
+    let types = {
+        let browser = state.browser.lock().unwrap();
+        browser.types.as_ref().map(summaries)
+    };
+    if let Some(types) = types {
+        let _ = app.emit("service-types-changed", &types);
+    }
+}
+
+/// Start browsing for `service_type`, streaming results as `browse-event`.
+/// Instances already resolved for the type are sent right away.
+pub fn start(app: &AppHandle, state: &AppState, service_type: &str) -> Result<String, AppError> {
+    let ty_domain = validate_type(service_type)?;
+
+    let cached: Vec<DiscoveredService> = {
+        let mut browser = state.browser.lock().unwrap();
+        if !browser.shown.insert(ty_domain.clone()) {
+            return Ok(ty_domain);
+        }
+        browser
+            .resolved
+            .get(&ty_domain)
+            .map(|r| r.values().cloned().collect())
+            .unwrap_or_default()
+    };
+    if let Err(e) = query(app, state, &ty_domain) {
+        state.browser.lock().unwrap().shown.remove(&ty_domain);
+        return Err(e);
+    }
+
+    logging::append_log(
+        app,
+        state,
+        LogLevel::Info,
+        format!("Browsing for {}", ty_domain),
+        None,
+    );
+    for service in cached {
+        let _ = app.emit("browse-event", &BrowseEvent::Resolved { service });
+    }
+    Ok(ty_domain)
+}
+
+/// Stop streaming results for `service_type`. The query itself keeps running
+/// while type enumeration or the watchlist still needs it.
+pub fn stop(app: &AppHandle, state: &AppState, service_type: &str) -> Result<(), AppError> {
+    let ty_domain = validate_type(service_type)?;
+    let keep_query = {
+        let mut browser = state.browser.lock().unwrap();
+        if !browser.shown.remove(&ty_domain) {
+            return Err(AppError::NotFound(ty_domain));
+        }
+        browser.in_use(&ty_domain)
+    };
+    if !keep_query {
+        let daemon = browse_daemon(state)?;
+        daemon
+            .stop_browse(&ty_domain)
+            .map_err(|e| AppError::Mdns(e.to_string()))?;
+    }
+    let _ = app.emit(
+        "browse-event",
+        &BrowseEvent::Stopped {
+            service_type: ty_domain,
+        },
+    );
+    Ok(())
+}
+
+/// Start enumerating service types, if not already running, and return the
+/// types seen so far. Updates follow as `service-types-changed`.
+pub fn enumerate_types(
+    app: &AppHandle,
+    state: &AppState,
+) -> Result<Vec<ServiceTypeSummary>, AppError> {
+    let (started, types) = {
+        let mut browser = state.browser.lock().unwrap();
+        let started = browser.types.is_none();
+        let types = browser.types.get_or_insert_with(BTreeMap::new);
+        (started, summaries(types))
+    };
+    if started {
+        if let Err(e) = query(app, state, META_QUERY) {
+            state.browser.lock().unwrap().types = None;
+            return Err(e);
+        }
+        logging::append_log(
+            app,
+            state,
+            LogLevel::Info,
+            "Enumerating service types".to_string(),
+            None,
+        );
+    }
+    Ok(types)
+}
+
+/// Stop enumerating service types, and the per-type queries it started for
+/// types nothing else needs.
+pub fn stop_enumeration(state: &AppState) -> Result<(), AppError> {
+    let stale: Vec<String> = {
+        let mut browser = state.browser.lock().unwrap();
+        if browser.types.take().is_none() {
+            return Ok(());
+        }
+        browser
+            .queries
+            .iter()
+            .filter(|ty| !browser.in_use(ty))
+            .cloned()
+            .collect()
+    };
+    let daemon = browse_daemon(state)?;
+    for ty in stale {
+        let _ = daemon.stop_browse(&ty);
+    }
+    Ok(())
+}
+
+/// Resolve a remote instance by fullname, from the browse cache if its type
+/// is already being queried or by browsing its type until it shows up.
+pub fn resolve_instance(
+    app: &AppHandle,
+    state: &AppState,
+    fullname: &str,
+) -> Result<DiscoveredService, AppError> {
+    let (_, service_type) = mdns::split_fullname(fullname)
+        .ok_or_else(|| AppError::Config(format!("Invalid service name '{}'", fullname)))?;
+    let ty_domain = mdns::to_mdns_type(&service_type);
+
+    let key = fullname.to_ascii_lowercase();
+    let (sender, receiver) = mpsc::channel();
+    let (started, cached, token) = {
+        let mut browser = state.browser.lock().unwrap();
+        let cached = browser.resolved.get(&ty_domain).and_then(|resolved| {
+            resolved
+                .values()
+                .find(|s| s.fullname.eq_ignore_ascii_case(fullname))
+                .cloned()
+        });
+        browser.next_token += 1;
+        let token = browser.next_token;
+        if cached.is_none() {
+            browser.resolving.push(ResolveWaiter {
+                token,
+                fullname: key,
+                sender,
+            });
+        }
+        (!browser.queries.contains(&ty_domain), cached, token)
+    };
+
+    let found = match cached {
+        Some(found) => Some(found),
+        None => {
+            let result =
+                query(app, state, &ty_domain).map(|()| receiver.recv_timeout(RESOLVE_TIMEOUT).ok());
+            state
+                .browser
+                .lock()
+                .unwrap()
+                .resolving
+                .retain(|w| w.token != token);
+            result?
+        }
+    };
+
+    // Only stop a query this call started and nothing else has picked up
+    if started {
+        let unused = !state.browser.lock().unwrap().in_use(&ty_domain);
+        if unused {
+            if let Ok(daemon) = browse_daemon(state) {
+                let _ = daemon.stop_browse(&ty_domain);
+            }
+        }
+    }
+
+    found.ok_or_else(|| AppError::NotFound(fullname.to_string()))
+}
+
+/// Shut down the browse daemon, if one was started.
+pub fn shutdown(state: &AppState) {
+    let daemon = state.browser.lock().unwrap().daemon.take();
+    if let Some(daemon) = daemon {
+        let _ = mdns::shutdown_daemon(&daemon);
+    }
+}
+
+fn discovered(app: &AppHandle, resolved: &ResolvedService) -> DiscoveredService {
+    let service_type = resolved.ty_domain.clone();
+    let name = mdns::instance_name_of(&resolved.fullname, &service_type)
+        .unwrap_or_else(|| resolved.fullname.clone());
+    let mut addresses: Vec<_> = resolved.addresses.iter().map(|a| a.to_ip_addr()).collect();
+    addresses.sort();
+    DiscoveredService {
+        own: is_own(app, &resolved.fullname),
+        fullname: resolved.fullname.clone(),
+        name,
+        service_type,
+        host: resolved.host.clone(),
+        port: resolved.port,
+        addresses,
+        txt: resolved
+            .txt_properties
+            .iter()
+            .map(txt::from_property)
+            .collect(),
+    }
+}
+
+/// Whether `fullname` is one of the services this app is publishing.
+fn is_own(app: &AppHandle, fullname: &str) -> bool {
+    let state = app.state::<AppState>();
+    let published = state.published.lock().unwrap();
+    published
+        .values()
+        .any(|r| r.fullname.eq_ignore_ascii_case(fullname))
+}
+
+/// Whether `fullname` is the placeholder instance one of our host aliases
+/// is published under.
+fn is_alias_placeholder(state: &AppState, fullname: &str) -> bool {
+    let config = state.config.lock().unwrap();
+    config
+        .aliases
+        .iter()
+        .filter_map(|a| mdns::alias_fullname(a).ok())
+        .any(|name| name.eq_ignore_ascii_case(fullname))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_type_qualifies_and_rejects() {
+        assert_eq!(validate_type(" _http._tcp ").unwrap(), "_http._tcp.local.");
+        assert!(validate_type("").is_err());
+        assert!(validate_type("http").is_err());
+    }
+
+    #[test]
+    fn validate_type_keeps_subtype() {
+        assert_eq!(
+            validate_type("_printer._sub._http._tcp").unwrap(),
+            "_printer._sub._http._tcp.local."
+        );
+        assert!(validate_type("_a b._sub._http._tcp").is_err());
+    }
+
+    #[test]
+    fn summaries_count_instances() {
+        let now = Utc::now();
+        let mut types = BTreeMap::new();
+        types.insert(
+            "_http._tcp.local.".to_string(),
+            TypeSighting {
+                first_seen: now,
+                last_seen: now,
+                instances: ["A._http._tcp.local.", "B._http._tcp.local."]
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            },
+        );
+        types.insert(
+            "_ipp._tcp.local.".to_string(),
+            TypeSighting {
+                first_seen: now,
+                last_seen: now,
+                instances: HashSet::new(),
+            },
+        );
+        let summary = summaries(&types);
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].service_type, "_http._tcp.local.");
+        assert_eq!(summary[0].instances, 2);
+        assert_eq!(summary[1].instances, 0);
+    }
+}
@@ -1,19 +1,41 @@
+use crate::binding;
+use crate::browse;
 use crate::config::save_config;
 use crate::error::AppError;
+use crate::health;
 use crate::logging;
 use crate::mdns;
 use crate::models::{
-    AppConfig, LogEntry, LogLevel, NetworkInterface, ServiceConfig, ServiceStatus, ServiceView,
+    AliasView, AppConfig, HostAlias, HostInfo, LogEntry, LogLevel, NetworkInterface, ProxyTarget,
+    PublishedRecord, ServiceConfig, ServiceInput, ServiceStatus, ServiceTypeSummary, ServiceView,
+    WatchEntry,
 };
 use crate::network;
+use crate::process;
+use crate::reconcile;
+use crate::schedule;
 use crate::state::AppState;
-use std::collections::HashMap;
+use crate::template;
+use crate::txt;
+use crate::txt_source;
+use crate::verify;
+use crate::watch;
+use chrono::Utc;
+use std::net::IpAddr;
 use tauri::{AppHandle, Emitter, State};
 use uuid::Uuid;
 
-fn build_views(state: &AppState) -> Result<Vec<ServiceView>, AppError> {
+/// Longest time-limited start: one day.
+const MAX_TIME_LIMIT_MINUTES: u32 = 24 * 60;
+
+pub(crate) fn build_views(state: &AppState) -> Result<Vec<ServiceView>, AppError> {
     let config = state.config.lock().unwrap();
     let statuses = state.statuses.lock().unwrap();
+    let published = state.published.lock().unwrap();
+    let verifications = state.verifications.lock().unwrap();
+    let processes = state.processes.lock().unwrap();
+    let stop_at = state.stop_at.lock().unwrap();
+    let reconciler = state.reconciler.lock().unwrap();
     Ok(config
         .services
         .iter()
@@ -22,25 +44,281 @@ fn build_views(state: &AppState) -> Result<Vec<ServiceView>, AppError> {
                 .get(&svc.id)
                 .copied()
                 .unwrap_or(ServiceStatus::Stopped);
-            ServiceView::from_config(svc, status)
+            let mut view = ServiceView::from_config(svc, status);
+            if let Some(record) = published.get(&svc.id) {
+                let registered = &record.config;
+                view.rendered_name = (registered.name != svc.name).then(|| registered.name.clone());
+                view.rendered_txt = (record.txt != svc.txt).then(|| record.txt.clone());
+                view.published_name =
+                    (record.instance_name != registered.name).then(|| record.instance_name.clone());
+                view.published_at = Some(record.published_at);
+            }
+            view.pid = processes.get(&svc.id).map(|p| p.pid);
+            view.stops_at = stop_at.get(&svc.id).copied();
+            if svc.enabled {
+                view.next_transition = svc
+                    .schedule
+                    .as_ref()
+                    .and_then(schedule::next_transition_utc);
+            }
+            if status == ServiceStatus::Running {
+                view.verification = verifications.get(&svc.id).cloned();
+            }
+            if status == ServiceStatus::Retrying {
+                if let Some(retry) = reconciler.retries.get(&svc.id) {
+                    view.retry_attempt = Some(retry.attempts);
+                    view.next_retry = Some(retry.next_attempt);
+                }
+            }
+            view
         })
         .collect())
 }
 
-/// Register a service via mDNS, update its status, and log the result.
+fn build_alias_views(state: &AppState) -> Result<Vec<AliasView>, AppError> {
+    let config = state.config.lock().unwrap();
+    let statuses = state.statuses.lock().unwrap();
+    Ok(config
+        .aliases
+        .iter()
+        .map(|alias| {
+            let status = statuses
+                .get(&alias.id)
+                .copied()
+                .unwrap_or(ServiceStatus::Stopped);
+            AliasView::from_config(alias, status)
+        })
+        .collect())
+}
+
+/// The config as it is on the wire: `svc` with its templates expanded as
+/// they were at registration, under the name conflict resolution chose.
+pub(crate) fn published_config(state: &AppState, svc: &ServiceConfig) -> ServiceConfig {
+    let published = state.published.lock().unwrap();
+    match published.get(&svc.id) {
+        Some(record) => record.announced(),
+        None => svc.clone(),
+    }
+}
+
+/// Remember what a registration put on the wire.
+fn record_published(
+    state: &AppState,
+    registered: ServiceConfig,
+    hostname: &str,
+    interfaces: &[String],
+) {
+    let host = mdns::service_host(&registered, hostname).unwrap_or_default();
+    let addresses = match &registered.proxy {
+        Some(proxy) => proxy.addresses.iter().map(ToString::to_string).collect(),
+        None => network::get_interfaces()
+            .into_iter()
+            .filter(|i| interfaces.is_empty() || interfaces.contains(&i.name))
+            .flat_map(|i| i.addresses)
+            .collect(),
+    };
+    let fullname = mdns::service_fullname(&registered);
+    let record = PublishedRecord {
+        service_id: registered.id.clone(),
+        registered_fullname: fullname.clone(),
+        fullname,
+        instance_name: registered.name.clone(),
+        host,
+        port: registered.port,
+        txt: registered.txt.clone(),
+        addresses,
+        interfaces: interfaces.to_vec(),
+        published_at: Utc::now(),
+        config: registered,
+    };
+    let mut published = state.published.lock().unwrap();
+    published.insert(record.service_id.clone(), record);
+}
+
+/// Note that a published service moved to another instance name, as
+/// mdns-sd does when probing finds its name taken. The registration stays
+/// keyed by the name it was registered under.
+pub(crate) fn record_instance_name(state: &AppState, id: &str, instance: &str) {
+    let mut published = state.published.lock().unwrap();
+    if let Some(record) = published.get_mut(id) {
+        record.instance_name = instance.to_string();
+        record.fullname = format!(
+            "{}.{}",
+            instance,
+            mdns::to_mdns_type(&record.config.service_type)
+        );
+    }
+}
+
+/// Publish a service on the shared daemon. A service with an interface
+/// filter is announced only on the interfaces the filter selects.
+pub(crate) fn register_on_daemon(
+    state: &AppState,
+    svc: &ServiceConfig,
+    hostname: &str,
+) -> Result<(), AppError> {
+    let svc = &txt_source::apply(state, svc);
+    let names = match &svc.interfaces {
+        Some(filter) => network::select_interfaces(filter, &network::get_interfaces())?,
+        None => Vec::new(),
+    };
+    let registered = {
+        let daemon = state.daemon.lock().unwrap();
+        mdns::register_service(&daemon, svc, hostname, &names)?
+    };
+    record_published(state, registered, hostname, &names);
+    Ok(())
+}
+
+/// Forget what was published for a service. Returns the record to withdraw.
+fn take_published(state: &AppState, svc: &ServiceConfig) -> Option<PublishedRecord> {
+    let record = {
+        let mut published = state.published.lock().unwrap();
+        published.remove(&svc.id)
+    };
+    {
+        let mut verifications = state.verifications.lock().unwrap();
+        verifications.remove(&svc.id);
+    }
+    record
+}
+
+/// Withdraw a service from the daemon, using the fullname recorded at
+/// registration. Nothing is sent for a service that was not published.
+pub(crate) fn unregister_on_daemon(
+    state: &AppState,
+    svc: &ServiceConfig,
+    wait: bool,
+) -> Result<(), AppError> {
+    if wait {
+        return unregister_and_wait(state, std::slice::from_ref(svc))
+            .pop()
+            .unwrap_or(Ok(()));
+    }
+    let Some(record) = take_published(state, svc) else {
+        return Ok(());
+    };
+    let daemon = state.daemon.lock().unwrap();
+    mdns::unregister_service(&daemon, &record.registered_fullname).map(drop)
+}
+
+/// Withdraw `services` and wait for their goodbye records. All unregisters
+/// are sent before the first wait, and the waits share one deadline without
+/// holding the daemon lock. Returns one result per service.
+pub(crate) fn unregister_and_wait(
+    state: &AppState,
+    services: &[ServiceConfig],
+) -> Vec<Result<(), AppError>> {
+    let records: Vec<Option<PublishedRecord>> = services
+        .iter()
+        .map(|svc| take_published(state, svc))
+        .collect();
+    let sent: Vec<_> = {
+        let daemon = state.daemon.lock().unwrap();
+        records
+            .iter()
+            .map(|record| {
+                record
+                    .as_ref()
+                    .map(|r| mdns::unregister_service(&daemon, &r.registered_fullname))
+            })
+            .collect()
+    };
+    let deadline = mdns::goodbye_deadline();
+    records
+        .iter()
+        .zip(sent)
+        .map(|(record, sent)| match (record, sent) {
+            (Some(record), Some(Ok(receiver))) => {
+                mdns::wait_for_goodbye(&receiver, &record.registered_fullname, deadline)
+            }
+            (_, Some(Err(e))) => Err(e),
+            _ => Ok(()),
+        })
+        .collect()
+}
+
+/// Start the service's process if it has one, register it via mDNS, update
+/// its status, and log the result.
 pub(crate) fn try_register_service(
     app: &AppHandle,
     state: &AppState,
     svc: &ServiceConfig,
     hostname: &str,
 ) {
-    let daemon = state.daemon.lock().unwrap();
-    let mut statuses = state.statuses.lock().unwrap();
-    match mdns::register_service(&daemon, svc, hostname) {
+    if let Some(deadline) = svc.stop_at {
+        let mut stop_at = state.stop_at.lock().unwrap();
+        stop_at.insert(svc.id.clone(), deadline);
+    }
+    if let Some(window) = svc.schedule.as_ref().filter(|s| !schedule::is_open_now(s)) {
+        {
+            let mut statuses = state.statuses.lock().unwrap();
+            statuses.insert(svc.id.clone(), ServiceStatus::Scheduled);
+        }
+        logging::append_log(
+            app,
+            state,
+            LogLevel::Info,
+            schedule::closed_message(svc, window),
+            Some(svc.id.clone()),
+        );
+        return;
+    }
+
+    if svc
+        .bound_process
+        .as_ref()
+        .is_some_and(|b| binding::find(b).is_none())
+    {
+        {
+            let mut statuses = state.statuses.lock().unwrap();
+            statuses.insert(svc.id.clone(), ServiceStatus::Waiting);
+        }
+        logging::append_log(
+            app,
+            state,
+            LogLevel::Info,
+            format!("Service '{}' is waiting for its bound process", svc.name),
+            Some(svc.id.clone()),
+        );
+        return;
+    }
+
+    if health::must_wait(state, svc) {
+        if let Err(e) = process::ensure_started(app, state, svc) {
+            process::stop(state, &svc.id);
+            registration_failed(app, state, svc, "Failed to start", &e);
+            return;
+        }
+        {
+            let mut statuses = state.statuses.lock().unwrap();
+            statuses.insert(svc.id.clone(), ServiceStatus::Waiting);
+        }
+        logging::append_log(
+            app,
+            state,
+            LogLevel::Info,
+            format!(
+                "Service '{}' is waiting for its first successful health check",
+                svc.name
+            ),
+            Some(svc.id.clone()),
+        );
+        return;
+    }
+
+    let result = process::ensure_started(app, state, svc)
+        .and_then(|()| register_on_daemon(state, svc, hostname));
+    if result.is_err() {
+        process::stop(state, &svc.id);
+    }
+    match result {
         Ok(()) => {
-            statuses.insert(svc.id.clone(), ServiceStatus::Running);
-            drop(statuses);
-            drop(daemon);
+            {
+                let mut statuses = state.statuses.lock().unwrap();
+                statuses.insert(svc.id.clone(), ServiceStatus::Running);
+            }
+            reconcile::clear_retry(state, &svc.id);
             logging::append_log(
                 app,
                 state,
@@ -48,29 +326,251 @@ pub(crate) fn try_register_service(
                 format!("Service '{}' started", svc.name),
                 Some(svc.id.clone()),
             );
+            verify::spawn_verification(app, svc, hostname);
+        }
+        Err(e) => registration_failed(app, state, svc, "Failed to start", &e),
+    }
+}
+
+/// Mark a service whose registration failed as Retrying, or as Error once
+/// its retry policy is used up, and log why.
+pub(crate) fn registration_failed(
+    app: &AppHandle,
+    state: &AppState,
+    svc: &ServiceConfig,
+    action: &str,
+    error: &AppError,
+) {
+    let retry = reconcile::record_failure(state, svc);
+    {
+        let mut statuses = state.statuses.lock().unwrap();
+        let status = match retry {
+            Some(_) => ServiceStatus::Retrying,
+            None => ServiceStatus::Error,
+        };
+        statuses.insert(svc.id.clone(), status);
+    }
+    let outcome = match retry {
+        Some(retry) => {
+            let policy = svc.retry.clone().unwrap_or_default();
+            let delay = reconcile::backoff(&policy, retry.attempts);
+            format!("retry {} in {}s", retry.attempts, delay.as_secs())
         }
+        None => "giving up".to_string(),
+    };
+    logging::append_log(
+        app,
+        state,
+        LogLevel::Error,
+        format!("{} service '{}': {} ({})", action, svc.name, error, outcome),
+        Some(svc.id.clone()),
+    );
+}
+
+/// Unregister a service via mDNS, stop its process, drop its time limit,
+/// sourced TXT values, health results and pending retry, and set its status
+/// to Stopped.
+pub(crate) fn try_unregister_service(state: &AppState, svc: &ServiceConfig) {
+    let _ = unregister_on_daemon(state, svc, false);
+    process::stop(state, &svc.id);
+    health::forget(state, &svc.id);
+    {
+        let mut stop_at = state.stop_at.lock().unwrap();
+        stop_at.remove(&svc.id);
+    }
+    {
+        let mut txt_values = state.txt_values.lock().unwrap();
+        txt_values.remove(&svc.id);
+    }
+    reconcile::clear_retry(state, &svc.id);
+    let mut statuses = state.statuses.lock().unwrap();
+    statuses.insert(svc.id.clone(), ServiceStatus::Stopped);
+}
+
+/// Re-announce the changed records of a running service on the daemon that
+/// published it, without withdrawing it first.
+fn update_on_daemon(
+    state: &AppState,
+    old: &ServiceConfig,
+    new: &ServiceConfig,
+    hostname: &str,
+) -> Result<(), AppError> {
+    let new = &txt_source::apply(state, new);
+    let (registered, instance_name, interfaces) = {
+        let published = state.published.lock().unwrap();
+        let record = published
+            .get(&old.id)
+            .ok_or_else(|| AppError::Mdns(format!("'{}' is not published", old.name)))?;
+        (
+            record.config.clone(),
+            record.instance_name.clone(),
+            record.interfaces.clone(),
+        )
+    };
+    let registered = {
+        let daemon = state.daemon.lock().unwrap();
+        mdns::update_service(&daemon, &registered, new, hostname, &interfaces)?
+    };
+    record_published(state, registered, hostname, &interfaces);
+    record_instance_name(state, &new.id, &instance_name);
+    Ok(())
+}
+
+/// Bring the running registration of `old` up to date with `new`. Records
+/// are re-announced in place when the fullname and host stay the same;
+/// otherwise the service is withdrawn and published again. Its process
+/// keeps running either way.
+pub(crate) fn republish_service(
+    app: &AppHandle,
+    state: &AppState,
+    old: &ServiceConfig,
+    new: &ServiceConfig,
+    hostname: &str,
+) {
+    let updated =
+        mdns::can_update_in_place(old, new) && update_on_daemon(state, old, new, hostname).is_ok();
+    let result = if updated {
+        Ok(())
+    } else {
+        let _ = unregister_on_daemon(state, old, true);
+        register_on_daemon(state, new, hostname)
+    };
+    match result {
+        Ok(()) => verify::spawn_verification(app, new, hostname),
         Err(e) => {
-            statuses.insert(svc.id.clone(), ServiceStatus::Error);
+            process::stop(state, &new.id);
+            registration_failed(app, state, new, "Failed to republish", &e);
+        }
+    }
+}
+
+/// Publish a host alias, update its status, and log the result.
+pub(crate) fn try_register_alias(app: &AppHandle, state: &AppState, alias: &HostAlias) {
+    let result = {
+        let daemon = state.daemon.lock().unwrap();
+        mdns::register_alias(&daemon, alias)
+    };
+    let mut statuses = state.statuses.lock().unwrap();
+    match result {
+        Ok(()) => {
+            statuses.insert(alias.id.clone(), ServiceStatus::Running);
+            drop(statuses);
+            logging::append_log(
+                app,
+                state,
+                LogLevel::Info,
+                format!("Host alias '{}' started", alias.name),
+                Some(alias.id.clone()),
+            );
+        }
+        Err(e) => {
+            statuses.insert(alias.id.clone(), ServiceStatus::Error);
             drop(statuses);
-            drop(daemon);
             logging::append_log(
                 app,
                 state,
                 LogLevel::Error,
-                format!("Failed to start service '{}': {}", svc.name, e),
-                Some(svc.id.clone()),
+                format!("Failed to start host alias '{}': {}", alias.name, e),
+                Some(alias.id.clone()),
             );
         }
     }
 }
 
-/// Unregister a service via mDNS and set its status to Stopped.
-fn try_unregister_service(state: &AppState, svc: &ServiceConfig, hostname: &str) {
+/// Withdraw a host alias and set its status to Stopped.
+fn try_unregister_alias(state: &AppState, alias: &HostAlias) {
     let daemon = state.daemon.lock().unwrap();
-    let _ = mdns::unregister_service(&daemon, svc, hostname);
+    let _ = mdns::unregister_alias(&daemon, alias);
     drop(daemon);
     let mut statuses = state.statuses.lock().unwrap();
-    statuses.insert(svc.id.clone(), ServiceStatus::Stopped);
+    statuses.insert(alias.id.clone(), ServiceStatus::Stopped);
+}
+
+/// Withdraw every running service with goodbye packets, then shut down the
+/// mDNS daemon. Used on quit, on SIGTERM/SIGINT and by any non-GUI exit path;
+/// calling it more than once is harmless.
+pub(crate) fn shutdown_services(app: &AppHandle, state: &AppState) {
+    {
+        let mut shut_down = state.shut_down.lock().unwrap();
+        if *shut_down {
+            return;
+        }
+        *shut_down = true;
+    }
+
+    let (running, aliases) = {
+        let config = state.config.lock().unwrap();
+        let statuses = state.statuses.lock().unwrap();
+        let is_running = |id: &str| statuses.get(id).copied() == Some(ServiceStatus::Running);
+        let running: Vec<ServiceConfig> = config
+            .services
+            .iter()
+            .filter(|s| is_running(&s.id))
+            .cloned()
+            .collect();
+        let aliases: Vec<HostAlias> = config
+            .aliases
+            .iter()
+            .filter(|a| is_running(&a.id))
+            .cloned()
+            .collect();
+        (running, aliases)
+    };
+
+    // The daemon handles commands in order, so these goodbyes go out before
+    // the shutdown below is processed
+    for alias in &aliases {
+        try_unregister_alias(state, alias);
+    }
+
+    let mut failures = 0;
+    for (svc, result) in running.iter().zip(unregister_and_wait(state, &running)) {
+        match result {
+            Ok(()) => {
+                let mut statuses = state.statuses.lock().unwrap();
+                statuses.insert(svc.id.clone(), ServiceStatus::Stopped);
+            }
+            Err(e) => {
+                failures += 1;
+                logging::append_log(
+                    app,
+                    state,
+                    LogLevel::Warn,
+                    format!("Failed to withdraw service '{}': {}", svc.name, e),
+                    Some(svc.id.clone()),
+                );
+            }
+        }
+    }
+
+    process::stop_all(state);
+    browse::shutdown(state);
+
+    let result = {
+        let daemon = state.daemon.lock().unwrap();
+        mdns::shutdown_daemon(&daemon)
+    };
+    match result {
+        Ok(()) => logging::append_log(
+            app,
+            state,
+            LogLevel::Info,
+            format!(
+                "mDNS daemon shut down ({} of {} service{} withdrawn)",
+                running.len() - failures,
+                running.len(),
+                if running.len() == 1 { "" } else { "s" }
+            ),
+            None,
+        ),
+        Err(e) => logging::append_log(
+            app,
+            state,
+            LogLevel::Error,
+            format!("Failed to shut down mDNS daemon: {}", e),
+            None,
+        ),
+    }
 }
 
 #[tauri::command]
@@ -78,43 +578,211 @@ pub fn get_services(state: State<'_, AppState>) -> Result<Vec<ServiceView>, AppE
     build_views(&state)
 }
 
+/// Records currently on the wire, for debugging what peers should see.
+#[tauri::command]
+pub fn get_published_records(state: State<'_, AppState>) -> Vec<PublishedRecord> {
+    let published = state.published.lock().unwrap();
+    let mut records: Vec<PublishedRecord> = published.values().cloned().collect();
+    records.sort_by(|a, b| a.fullname.cmp(&b.fullname));
+    records
+}
+
+/// Normalise the user-entered parts of `svc` and reject settings that
+/// could not be published or run.
+fn validate_service(svc: &mut ServiceConfig) -> Result<(), AppError> {
+    if svc.name.trim().is_empty() {
+        return Err(AppError::Config("Service name must not be empty".into()));
+    }
+    if svc.service_type.trim().is_empty() {
+        return Err(AppError::Config("Service type must not be empty".into()));
+    }
+    txt::validate(&svc.txt)?;
+    template::validate_service(&svc.name, &svc.txt)?;
+    txt_source::validate(&svc.txt_sources)?;
+    if let Some(check) = &svc.health_check {
+        health::validate(check)?;
+    }
+    if let Some(process) = &svc.process {
+        process::validate(process)?;
+    }
+    if let Some(binding) = &svc.bound_process {
+        binding::validate(binding)?;
+    }
+    if let Some(schedule) = &svc.schedule {
+        schedule::validate(schedule)?;
+    }
+    if let Some(policy) = &svc.retry {
+        reconcile::validate(policy)?;
+    }
+    svc.subtype = svc
+        .subtype
+        .take()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    if let Some(subtype) = &svc.subtype {
+        mdns::validate_subtype(subtype)?;
+    }
+    if let Some(filter) = &svc.interfaces {
+        network::validate_filter(filter)?;
+    }
+    if let Some(proxy) = &svc.proxy {
+        mdns::validate_proxy(proxy)?;
+    }
+    svc.hostname = svc
+        .hostname
+        .take()
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty());
+    if let Some(name) = &svc.hostname {
+        mdns::normalize_hostname(name)?;
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub fn add_service(
     app: AppHandle,
     state: State<'_, AppState>,
-    name: String,
-    service_type: String,
-    port: u16,
-    txt: HashMap<String, String>,
-    enabled: bool,
+    service: ServiceInput,
 ) -> Result<Vec<ServiceView>, AppError> {
     let id = Uuid::new_v4().to_string();
-    let svc = ServiceConfig {
+    let mut svc = service.apply(&ServiceConfig {
         id: id.clone(),
-        name: name.clone(),
-        service_type,
-        port,
-        txt,
-        enabled,
-    };
+        ..Default::default()
+    });
+    validate_service(&mut svc)?;
 
     let hostname = {
         let mut config = state.config.lock().unwrap();
         config.services.push(svc.clone());
         save_config(&config)?;
-        config.hostname.clone()
+        config.effective_hostname().to_string()
     };
 
     logging::append_log(
         &app,
         &state,
         LogLevel::Info,
-        format!("Service '{}' added", name),
+        format!("Service '{}' added", svc.name),
         Some(id),
     );
 
-    if enabled {
-        try_register_service(&app, &state, &svc, &hostname);
+    reconcile::converge(&app, &state, &svc, &hostname);
+
+    let views = build_views(&state)?;
+    let _ = app.emit("services-changed", &views);
+    Ok(views)
+}
+
+/// Whether an edit of a running service can be applied by re-announcing its
+/// records, leaving its process, binding and schedule untouched.
+fn updatable_in_place(old: &ServiceConfig, new: &ServiceConfig) -> bool {
+    mdns::can_update_in_place(old, new)
+        && old.process == new.process
+        && old.bound_process == new.bound_process
+        && old.schedule == new.schedule
+}
+
+/// How an edit of a service reaches the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditPath {
+    /// Re-announce the records of the running registration.
+    InPlace,
+    /// Unregister and register again; the process keeps running.
+    Republish,
+    /// Withdraw the service, process included, and converge it to the edit.
+    Restart,
+}
+
+fn edit_path(old: &ServiceConfig, new: &ServiceConfig, status: Option<ServiceStatus>) -> EditPath {
+    if !new.enabled || old.process != new.process {
+        EditPath::Restart
+    } else if status == Some(ServiceStatus::Running) && updatable_in_place(old, new) {
+        EditPath::InPlace
+    } else if status.is_some_and(|s| s.is_active()) {
+        EditPath::Republish
+    } else {
+        EditPath::Restart
+    }
+}
+
+/// Take the registration of `old` down as far as the edit to `new` needs,
+/// before the edit is saved.
+fn prepare_edit(
+    state: &AppState,
+    old: &ServiceConfig,
+    new: &ServiceConfig,
+    status: Option<ServiceStatus>,
+) -> EditPath {
+    let path = edit_path(old, new, status);
+    match path {
+        EditPath::InPlace => {}
+        EditPath::Republish => {
+            let _ = unregister_on_daemon(state, old, false);
+        }
+        EditPath::Restart => reconcile::withdraw(state, old),
+    }
+    path
+}
+
+/// Apply `changes` to a service. Fields the frontend leaves out keep their
+/// current value.
+#[tauri::command]
+pub fn update_service(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+    changes: ServiceInput,
+) -> Result<Vec<ServiceView>, AppError> {
+    let status;
+    let old_config;
+    let hostname;
+
+    {
+        let config = state.config.lock().unwrap();
+        let statuses = state.statuses.lock().unwrap();
+        let svc = config
+            .services
+            .iter()
+            .find(|s| s.id == id)
+            .ok_or_else(|| AppError::NotFound(id.clone()))?;
+        status = statuses.get(&id).copied();
+        old_config = svc.clone();
+        hostname = config.effective_hostname().to_string();
+    }
+
+    let mut new_svc = changes.apply(&old_config);
+    validate_service(&mut new_svc)?;
+
+    // A published service whose edit only touches records keeps its
+    // registration, and only disabling it or changing its command stops
+    // its process
+    let path = prepare_edit(&state, &old_config, &new_svc, status);
+
+    {
+        let mut config = state.config.lock().unwrap();
+        if let Some(svc) = config.services.iter_mut().find(|s| s.id == id) {
+            svc.clone_from(&new_svc);
+        }
+        save_config(&config)?;
+    }
+
+    logging::append_log(
+        &app,
+        &state,
+        LogLevel::Info,
+        format!("Service '{}' updated", new_svc.name),
+        Some(id.clone()),
+    );
+
+    if old_config.txt_sources != new_svc.txt_sources {
+        let mut txt_values = state.txt_values.lock().unwrap();
+        txt_values.remove(&id);
+    }
+    match path {
+        EditPath::InPlace => republish_service(&app, &state, &old_config, &new_svc, &hostname),
+        EditPath::Republish => try_register_service(&app, &state, &new_svc, &hostname),
+        EditPath::Restart => reconcile::converge(&app, &state, &new_svc, &hostname),
     }
 
     let views = build_views(&state)?;
@@ -122,54 +790,247 @@ pub fn add_service(
     Ok(views)
 }
 
-#[allow(clippy::too_many_arguments)]
 #[tauri::command]
-pub fn update_service(
+pub fn delete_service(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Vec<ServiceView>, AppError> {
+    let svc_config = {
+        let config = state.config.lock().unwrap();
+        config
+            .services
+            .iter()
+            .find(|s| s.id == id)
+            .ok_or_else(|| AppError::NotFound(id.clone()))?
+            .clone()
+    };
+
+    reconcile::withdraw(&state, &svc_config);
+
+    {
+        let mut config = state.config.lock().unwrap();
+        config.services.retain(|s| s.id != id);
+        save_config(&config)?;
+    }
+
+    {
+        let mut statuses = state.statuses.lock().unwrap();
+        statuses.remove(&id);
+    }
+
+    logging::append_log(
+        &app,
+        &state,
+        LogLevel::Info,
+        format!("Service '{}' deleted", svc_config.name),
+        Some(id),
+    );
+
+    let views = build_views(&state)?;
+    let _ = app.emit("services-changed", &views);
+    Ok(views)
+}
+
+#[tauri::command]
+pub fn toggle_service(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Vec<ServiceView>, AppError> {
+    let svc_config;
+    let hostname;
+
+    {
+        let mut config = state.config.lock().unwrap();
+        let statuses = state.statuses.lock().unwrap();
+        let svc = config
+            .services
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| AppError::NotFound(id.clone()))?;
+        // A failed or stopped service is started, anything active is stopped
+        svc.enabled = !statuses.get(&id).is_some_and(|s| s.is_active());
+        svc.stop_at = None;
+        svc_config = svc.clone();
+        drop(statuses);
+        save_config(&config)?;
+        hostname = config.effective_hostname().to_string();
+    }
+
+    reconcile::converge(&app, &state, &svc_config, &hostname);
+    if !svc_config.enabled {
+        logging::append_log(
+            &app,
+            &state,
+            LogLevel::Info,
+            format!("Service '{}' stopped", svc_config.name),
+            Some(id),
+        );
+    }
+
+    let views = build_views(&state)?;
+    let _ = app.emit("services-changed", &views);
+    Ok(views)
+}
+
+/// Start a service and stop it again after `minutes`.
+#[tauri::command]
+pub fn start_service_for(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+    minutes: u32,
+) -> Result<Vec<ServiceView>, AppError> {
+    if minutes == 0 || minutes > MAX_TIME_LIMIT_MINUTES {
+        return Err(AppError::Config(format!(
+            "Time limit must be between 1 and {} minutes",
+            MAX_TIME_LIMIT_MINUTES
+        )));
+    }
+
+    let deadline = Utc::now() + chrono::Duration::minutes(i64::from(minutes));
+    let svc_config;
+    let hostname;
+    {
+        let mut config = state.config.lock().unwrap();
+        let svc = config
+            .services
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| AppError::NotFound(id.clone()))?;
+        svc.enabled = true;
+        svc.stop_at = Some(deadline);
+        svc_config = svc.clone();
+        save_config(&config)?;
+        hostname = config.effective_hostname().to_string();
+    }
+
+    reconcile::converge(&app, &state, &svc_config, &hostname);
+    {
+        let mut stop_at = state.stop_at.lock().unwrap();
+        stop_at.insert(id.clone(), deadline);
+    }
+    logging::append_log(
+        &app,
+        &state,
+        LogLevel::Info,
+        format!(
+            "Service '{}' will stop in {} minute{}",
+            svc_config.name,
+            minutes,
+            if minutes == 1 { "" } else { "s" }
+        ),
+        Some(id),
+    );
+
+    let views = build_views(&state)?;
+    let _ = app.emit("services-changed", &views);
+    Ok(views)
+}
+
+#[tauri::command]
+pub fn start_all(app: AppHandle, state: State<'_, AppState>) -> Result<Vec<ServiceView>, AppError> {
+    let services: Vec<ServiceConfig>;
+    let hostname: String;
+
+    {
+        let mut config = state.config.lock().unwrap();
+        for svc in config.services.iter_mut() {
+            svc.enabled = true;
+        }
+        save_config(&config)?;
+        services = config.services.clone();
+        hostname = config.effective_hostname().to_string();
+    }
+
+    for svc in &services {
+        reconcile::converge(&app, &state, svc, &hostname);
+    }
+
+    logging::append_log(
+        &app,
+        &state,
+        LogLevel::Info,
+        "All services started".to_string(),
+        None,
+    );
+
+    let views = build_views(&state)?;
+    let _ = app.emit("services-changed", &views);
+    Ok(views)
+}
+
+#[tauri::command]
+pub fn stop_all(app: AppHandle, state: State<'_, AppState>) -> Result<Vec<ServiceView>, AppError> {
+    let services: Vec<ServiceConfig>;
+    let hostname: String;
+
+    {
+        let mut config = state.config.lock().unwrap();
+        for svc in config.services.iter_mut() {
+            svc.enabled = false;
+            svc.stop_at = None;
+        }
+        save_config(&config)?;
+        services = config.services.clone();
+        hostname = config.effective_hostname().to_string();
+    }
+
+    for svc in &services {
+        reconcile::converge(&app, &state, svc, &hostname);
+    }
+
+    logging::append_log(
+        &app,
+        &state,
+        LogLevel::Info,
+        "All services stopped".to_string(),
+        None,
+    );
+
+    let views = build_views(&state)?;
+    let _ = app.emit("services-changed", &views);
+    Ok(views)
+}
+
+/// Retry failed registrations and correct drift right away instead of
+/// waiting for the next background pass.
+#[tauri::command]
+pub fn reconcile_services(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<ServiceView>, AppError> {
+    reconcile::reconcile(&app, &state, reconcile::Trigger::OnDemand);
+    build_views(&state)
+}
+
+#[tauri::command]
+pub fn get_aliases(state: State<'_, AppState>) -> Result<Vec<AliasView>, AppError> {
+    build_alias_views(&state)
+}
+
+#[tauri::command]
+pub fn add_alias(
     app: AppHandle,
     state: State<'_, AppState>,
-    id: String,
     name: String,
-    service_type: String,
-    port: u16,
-    txt: HashMap<String, String>,
+    addresses: Vec<IpAddr>,
     enabled: bool,
-) -> Result<Vec<ServiceView>, AppError> {
-    let was_running;
-    let old_config;
-    let hostname;
-
-    {
-        let config = state.config.lock().unwrap();
-        let statuses = state.statuses.lock().unwrap();
-        let svc = config
-            .services
-            .iter()
-            .find(|s| s.id == id)
-            .ok_or_else(|| AppError::NotFound(id.clone()))?;
-        was_running = statuses.get(&id).copied() == Some(ServiceStatus::Running);
-        old_config = svc.clone();
-        hostname = config.hostname.clone();
-    }
-
-    // Unregister old if running
-    if was_running {
-        try_unregister_service(&state, &old_config, &hostname);
-    }
+) -> Result<Vec<AliasView>, AppError> {
+    mdns::validate_alias_name(&name)?;
 
-    let new_svc = ServiceConfig {
+    let id = Uuid::new_v4().to_string();
+    let alias = HostAlias {
         id: id.clone(),
         name: name.clone(),
-        service_type,
-        port,
-        txt,
+        addresses,
         enabled,
     };
 
     {
         let mut config = state.config.lock().unwrap();
-        if let Some(svc) = config.services.iter_mut().find(|s| s.id == id) {
-            svc.clone_from(&new_svc);
-        }
+        config.aliases.push(alias.clone());
         save_config(&config)?;
     }
 
@@ -177,50 +1038,46 @@ pub fn update_service(
         &app,
         &state,
         LogLevel::Info,
-        format!("Service '{}' updated", name),
+        format!("Host alias '{}' added", name),
         Some(id),
     );
 
-    // Re-register if should be enabled
     if enabled {
-        try_register_service(&app, &state, &new_svc, &hostname);
+        try_register_alias(&app, &state, &alias);
     }
 
-    let views = build_views(&state)?;
-    let _ = app.emit("services-changed", &views);
+    let views = build_alias_views(&state)?;
+    let _ = app.emit("aliases-changed", &views);
     Ok(views)
 }
 
 #[tauri::command]
-pub fn delete_service(
+pub fn delete_alias(
     app: AppHandle,
     state: State<'_, AppState>,
     id: String,
-) -> Result<Vec<ServiceView>, AppError> {
-    let svc_config;
+) -> Result<Vec<AliasView>, AppError> {
+    let alias;
     let is_running;
-    let hostname;
     {
         let config = state.config.lock().unwrap();
         let statuses = state.statuses.lock().unwrap();
-        svc_config = config
-            .services
+        alias = config
+            .aliases
             .iter()
-            .find(|s| s.id == id)
+            .find(|a| a.id == id)
             .ok_or_else(|| AppError::NotFound(id.clone()))?
             .clone();
         is_running = statuses.get(&id).copied() == Some(ServiceStatus::Running);
-        hostname = config.hostname.clone();
     }
 
-    // Unregister if running
     if is_running {
-        try_unregister_service(&state, &svc_config, &hostname);
+        try_unregister_alias(&state, &alias);
     }
 
     {
         let mut config = state.config.lock().unwrap();
-        config.services.retain(|s| s.id != id);
+        config.aliases.retain(|a| a.id != id);
         save_config(&config)?;
     }
 
@@ -233,177 +1090,326 @@ pub fn delete_service(
         &app,
         &state,
         LogLevel::Info,
-        format!("Service '{}' deleted", svc_config.name),
+        format!("Host alias '{}' deleted", alias.name),
         Some(id),
     );
 
-    let views = build_views(&state)?;
-    let _ = app.emit("services-changed", &views);
+    let views = build_alias_views(&state)?;
+    let _ = app.emit("aliases-changed", &views);
     Ok(views)
 }
 
 #[tauri::command]
-pub fn toggle_service(
+pub fn toggle_alias(
     app: AppHandle,
     state: State<'_, AppState>,
     id: String,
-) -> Result<Vec<ServiceView>, AppError> {
-    let svc_config;
+) -> Result<Vec<AliasView>, AppError> {
+    let alias;
     let currently_running;
-    let hostname;
-
     {
         let config = state.config.lock().unwrap();
         let statuses = state.statuses.lock().unwrap();
-        svc_config = config
-            .services
+        alias = config
+            .aliases
             .iter()
-            .find(|s| s.id == id)
+            .find(|a| a.id == id)
             .ok_or_else(|| AppError::NotFound(id.clone()))?
             .clone();
         currently_running = statuses.get(&id).copied() == Some(ServiceStatus::Running);
-        hostname = config.hostname.clone();
     }
 
     if currently_running {
-        // Stop
-        try_unregister_service(&state, &svc_config, &hostname);
-        {
-            let mut config = state.config.lock().unwrap();
-            if let Some(svc) = config.services.iter_mut().find(|s| s.id == id) {
-                svc.enabled = false;
-            }
-            save_config(&config)?;
-        }
+        try_unregister_alias(&state, &alias);
         logging::append_log(
             &app,
             &state,
             LogLevel::Info,
-            format!("Service '{}' stopped", svc_config.name),
-            Some(id),
+            format!("Host alias '{}' stopped", alias.name),
+            Some(id.clone()),
         );
     } else {
-        // Start
-        try_register_service(&app, &state, &svc_config, &hostname);
-        {
-            let mut config = state.config.lock().unwrap();
-            if let Some(svc) = config.services.iter_mut().find(|s| s.id == id) {
-                svc.enabled = true;
-            }
-            save_config(&config)?;
+        try_register_alias(&app, &state, &alias);
+    }
+
+    {
+        let mut config = state.config.lock().unwrap();
+        if let Some(a) = config.aliases.iter_mut().find(|a| a.id == id) {
+            a.enabled = !currently_running;
         }
+        save_config(&config)?;
     }
 
-    let views = build_views(&state)?;
-    let _ = app.emit("services-changed", &views);
+    let views = build_alias_views(&state)?;
+    let _ = app.emit("aliases-changed", &views);
     Ok(views)
 }
 
 #[tauri::command]
-pub fn start_all(app: AppHandle, state: State<'_, AppState>) -> Result<Vec<ServiceView>, AppError> {
-    let services: Vec<ServiceConfig>;
-    let hostname: String;
+pub fn get_host_name(state: State<'_, AppState>) -> String {
+    let config = state.config.lock().unwrap();
+    config.effective_hostname().to_string()
+}
 
-    {
-        let config = state.config.lock().unwrap();
-        services = config.services.clone();
-        hostname = config.hostname.clone();
+#[tauri::command]
+pub fn get_host_info(state: State<'_, AppState>) -> HostInfo {
+    let config = state.config.lock().unwrap();
+    HostInfo {
+        effective: config.effective_hostname().to_string(),
+        os: config.hostname.clone(),
+        hostname_override: config.hostname_override.clone(),
     }
+}
 
-    for svc in &services {
-        let is_running = {
-            let statuses = state.statuses.lock().unwrap();
-            statuses.get(&svc.id).copied() == Some(ServiceStatus::Running)
-        };
-        if !is_running {
-            try_register_service(&app, &state, svc, &hostname);
-        }
-    }
+/// Set or clear the global hostname override and re-register every running
+/// service that is advertised under the global hostname.
+#[tauri::command]
+pub fn set_hostname_override(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    hostname: Option<String>,
+) -> Result<HostInfo, AppError> {
+    // Stored without the `.local` suffix, like the OS hostname
+    let hostname = match hostname.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => Some(
+            mdns::normalize_hostname(name)?
+                .trim_end_matches(".local.")
+                .to_string(),
+        ),
+        _ => None,
+    };
 
+    let old_hostname;
+    let new_hostname;
+    let affected: Vec<ServiceConfig>;
     {
         let mut config = state.config.lock().unwrap();
-        for svc in config.services.iter_mut() {
-            svc.enabled = true;
-        }
+        old_hostname = config.effective_hostname().to_string();
+        config.hostname_override = hostname;
         save_config(&config)?;
+        new_hostname = config.effective_hostname().to_string();
+
+        let statuses = state.statuses.lock().unwrap();
+        affected = config
+            .services
+            .iter()
+            .filter(|s| statuses.get(&s.id).copied() == Some(ServiceStatus::Running))
+            .filter(|s| s.proxy.is_none() && s.hostname.is_none())
+            .cloned()
+            .collect();
+    }
+
+    if old_hostname != new_hostname {
+        for svc in &affected {
+            reconcile::republish(&app, &state, svc, &new_hostname);
+        }
+
+        logging::append_log(
+            &app,
+            &state,
+            LogLevel::Info,
+            format!(
+                "Hostname changed from '{}' to '{}' ({} service{} re-registered)",
+                old_hostname,
+                new_hostname,
+                affected.len(),
+                if affected.len() == 1 { "" } else { "s" }
+            ),
+            None,
+        );
+
+        let views = build_views(&state)?;
+        let _ = app.emit("services-changed", &views);
     }
 
+    Ok(get_host_info(state))
+}
+
+#[tauri::command]
+pub fn get_event_logs(state: State<'_, AppState>) -> Vec<LogEntry> {
+    let logs = state.logs.lock().unwrap();
+    logs.iter().cloned().collect()
+}
+
+#[tauri::command]
+pub fn clear_event_logs(state: State<'_, AppState>) {
+    let mut logs = state.logs.lock().unwrap();
+    logs.clear();
+}
+
+#[tauri::command]
+pub fn get_network_interfaces() -> Vec<NetworkInterface> {
+    network::get_interfaces()
+}
+
+/// Start browsing for a service type. Returns the fully qualified type that
+/// `browse-event` payloads refer to.
+#[tauri::command]
+pub fn start_browse(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    service_type: String,
+) -> Result<String, AppError> {
+    browse::start(&app, &state, &service_type)
+}
+
+#[tauri::command]
+pub fn stop_browse(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    service_type: String,
+) -> Result<(), AppError> {
+    browse::stop(&app, &state, &service_type)
+}
+
+/// Service types seen on the network. The first call starts enumerating;
+/// updates follow as `service-types-changed`.
+#[tauri::command]
+pub fn get_service_types(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<ServiceTypeSummary>, AppError> {
+    browse::enumerate_types(&app, &state)
+}
+
+/// Resolve a service on the network by fullname and add it as a new local
+/// service, optionally advertised as a proxy for the remote host. The clone
+/// is added disabled unless `enabled` is set, since it would otherwise
+/// conflict with the original straight away.
+///
+/// Runs off the main thread because resolving can take a few seconds.
+#[tauri::command(async)]
+pub fn clone_discovered_service(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    fullname: String,
+    name: Option<String>,
+    as_proxy: bool,
+    enabled: Option<bool>,
+) -> Result<Vec<ServiceView>, AppError> {
+    let remote = browse::resolve_instance(&app, &state, fullname.trim())?;
+    let (instance, service_type) = mdns::split_fullname(&remote.fullname)
+        .ok_or_else(|| AppError::Config(format!("Invalid service name '{}'", remote.fullname)))?;
+    let name = name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or(instance);
+    let proxy = as_proxy.then(|| ProxyTarget {
+        host: remote
+            .host
+            .trim_end_matches('.')
+            .trim_end_matches(".local")
+            .to_string(),
+        addresses: remote.addresses.clone(),
+    });
+
     logging::append_log(
         &app,
         &state,
         LogLevel::Info,
-        "All services started".to_string(),
+        format!(
+            "Cloning '{}' from {}{}",
+            remote.fullname,
+            remote.host,
+            if as_proxy { " as a proxy" } else { "" }
+        ),
         None,
     );
 
-    let views = build_views(&state)?;
-    let _ = app.emit("services-changed", &views);
-    Ok(views)
+    add_service(
+        app,
+        state,
+        ServiceInput {
+            name: Some(name),
+            service_type: Some(service_type),
+            port: Some(remote.port),
+            txt: Some(remote.txt),
+            enabled: Some(enabled.unwrap_or(false)),
+            proxy: Some(proxy),
+            ..Default::default()
+        },
+    )
 }
 
 #[tauri::command]
-pub fn stop_all(app: AppHandle, state: State<'_, AppState>) -> Result<Vec<ServiceView>, AppError> {
-    let services: Vec<ServiceConfig>;
-    let hostname: String;
+pub fn stop_service_types(state: State<'_, AppState>) -> Result<(), AppError> {
+    browse::stop_enumeration(&state)
+}
 
-    {
-        let config = state.config.lock().unwrap();
-        services = config.services.clone();
-        hostname = config.hostname.clone();
-    }
+#[tauri::command]
+pub fn get_watchlist(state: State<'_, AppState>) -> Vec<WatchEntry> {
+    state.config.lock().unwrap().watchlist.clone()
+}
 
-    for svc in &services {
-        let is_running = {
-            let statuses = state.statuses.lock().unwrap();
-            statuses.get(&svc.id).copied() == Some(ServiceStatus::Running)
-        };
-        if is_running {
-            try_unregister_service(&state, svc, &hostname);
-        }
-    }
+/// Watch a remote instance (by fullname) or every instance of a type.
+#[tauri::command]
+pub fn add_watch(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    target: String,
+) -> Result<Vec<WatchEntry>, AppError> {
+    let target = target.trim().to_string();
+    watch::target_type(&target)?;
 
-    {
+    let watchlist = {
         let mut config = state.config.lock().unwrap();
-        for svc in config.services.iter_mut() {
-            svc.enabled = false;
+        if config
+            .watchlist
+            .iter()
+            .any(|w| w.target.eq_ignore_ascii_case(&target))
+        {
+            return Err(AppError::Config(format!("'{}' is already watched", target)));
         }
+        config.watchlist.push(WatchEntry {
+            id: Uuid::new_v4().to_string(),
+            target: target.clone(),
+        });
         save_config(&config)?;
-    }
+        config.watchlist.clone()
+    };
 
     logging::append_log(
         &app,
         &state,
         LogLevel::Info,
-        "All services stopped".to_string(),
+        format!("Watching '{}'", target),
         None,
     );
+    watch::sync(&app, &state);
 
-    let views = build_views(&state)?;
-    let _ = app.emit("services-changed", &views);
-    Ok(views)
-}
-
-#[tauri::command]
-pub fn get_host_name(state: State<'_, AppState>) -> String {
-    let config = state.config.lock().unwrap();
-    config.hostname.clone()
+    let _ = app.emit("watchlist-changed", &watchlist);
+    Ok(watchlist)
 }
 
 #[tauri::command]
-pub fn get_event_logs(state: State<'_, AppState>) -> Vec<LogEntry> {
-    let logs = state.logs.lock().unwrap();
-    logs.iter().cloned().collect()
-}
+pub fn delete_watch(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Vec<WatchEntry>, AppError> {
+    let (removed, watchlist) = {
+        let mut config = state.config.lock().unwrap();
+        let index = config
+            .watchlist
+            .iter()
+            .position(|w| w.id == id)
+            .ok_or_else(|| AppError::NotFound(id.clone()))?;
+        let removed = config.watchlist.remove(index);
+        save_config(&config)?;
+        (removed, config.watchlist.clone())
+    };
 
-#[tauri::command]
-pub fn clear_event_logs(state: State<'_, AppState>) {
-    let mut logs = state.logs.lock().unwrap();
-    logs.clear();
-}
+    logging::append_log(
+        &app,
+        &state,
+        LogLevel::Info,
+        format!("Stopped watching '{}'", removed.target),
+        None,
+    );
+    watch::sync(&app, &state);
 
-#[tauri::command]
-pub fn get_network_interfaces() -> Vec<NetworkInterface> {
-    network::get_interfaces()
+    let _ = app.emit("watchlist-changed", &watchlist);
+    Ok(watchlist)
 }
 
 #[tauri::command]
@@ -421,30 +1427,66 @@ pub fn import_config(
     let mut imported: AppConfig = serde_json::from_str(&json)
         .map_err(|e| AppError::Config(format!("Invalid JSON: {}", e)))?;
 
+    for svc in imported.services.iter_mut() {
+        validate_service(svc)
+            .map_err(|e| AppError::Config(format!("Service '{}': {}", svc.name, e)))?;
+    }
+    for alias in &imported.aliases {
+        mdns::validate_alias_name(&alias.name)?;
+    }
+    for entry in imported.watchlist.iter_mut() {
+        entry.target = entry.target.trim().to_string();
+        watch::target_type(&entry.target)
+            .map_err(|e| AppError::Config(format!("Watch '{}': {}", entry.target, e)))?;
+    }
+
     // Assign new UUIDs to avoid collisions
     for svc in imported.services.iter_mut() {
         svc.id = Uuid::new_v4().to_string();
     }
+    for alias in imported.aliases.iter_mut() {
+        alias.id = Uuid::new_v4().to_string();
+    }
+
+    // Nothing from a file gets to run commands before the user has seen them
+    let mut held_back = Vec::new();
+    for svc in imported.services.iter_mut() {
+        if svc.enabled && (svc.process.is_some() || !svc.txt_sources.is_empty()) {
+            svc.enabled = false;
+            held_back.push(svc.clone());
+        }
+    }
+    for entry in imported.watchlist.iter_mut() {
+        entry.id = Uuid::new_v4().to_string();
+    }
 
     // Stop all existing running services
+    let services = state.config.lock().unwrap().services.clone();
+    for svc in &services {
+        reconcile::withdraw(&state, svc);
+    }
     {
         let config = state.config.lock().unwrap();
-        let daemon = state.daemon.lock().unwrap();
         let mut statuses = state.statuses.lock().unwrap();
-        for svc in &config.services {
-            if statuses.get(&svc.id).copied() == Some(ServiceStatus::Running) {
-                let _ = mdns::unregister_service(&daemon, svc, &config.hostname);
+        let daemon = state.daemon.lock().unwrap();
+        for alias in &config.aliases {
+            if statuses.get(&alias.id).copied() == Some(ServiceStatus::Running) {
+                let _ = mdns::unregister_alias(&daemon, alias);
             }
         }
         statuses.clear();
     }
+    process::stop_all(&state);
 
-    // Preserve current hostname (not from imported config)
+    // Preserve current hostname and override (not from imported config)
     let hostname = {
         let config = state.config.lock().unwrap();
-        config.hostname.clone()
+        imported.hostname.clone_from(&config.hostname);
+        imported
+            .hostname_override
+            .clone_from(&config.hostname_override);
+        imported.effective_hostname().to_string()
     };
-    imported.hostname.clone_from(&hostname);
 
     // Replace config and save
     {
@@ -455,10 +1497,14 @@ pub fn import_config(
 
     // Start enabled services
     for svc in &imported.services {
-        if svc.enabled {
-            try_register_service(&app, &state, svc, &hostname);
+        reconcile::converge(&app, &state, svc, &hostname);
+    }
+    for alias in &imported.aliases {
+        if alias.enabled {
+            try_register_alias(&app, &state, alias);
         }
     }
+    watch::sync(&app, &state);
 
     logging::append_log(
         &app,
@@ -475,8 +1521,226 @@ pub fn import_config(
         ),
         None,
     );
+    for svc in &held_back {
+        logging::append_log(
+            &app,
+            &state,
+            LogLevel::Warn,
+            format!(
+                "Service '{}' imported disabled as it runs commands; review it before starting",
+                svc.name
+            ),
+            Some(svc.id.clone()),
+        );
+    }
 
     let views = build_views(&state)?;
     let _ = app.emit("services-changed", &views);
     Ok(views)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        ConflictPolicy, HealthCheck, HealthProbe, InterfaceFilter, InterfaceFilterMode,
+        ManagedProcess, ProcessBinding, RetryPolicy, Schedule, TxtEntry, TxtSource, TxtSourceKind,
+    };
+    use mdns_sd::UnregisterStatus;
+    use std::time::Duration;
+
+    #[test]
+    fn updatable_in_place_covers_records_only() {
+        let old = ServiceConfig {
+            id: "web".into(),
+            name: "Web".into(),
+            service_type: "_http._tcp".into(),
+            port: 8080,
+            enabled: true,
+            ..Default::default()
+        };
+        let edit = |change: fn(&mut ServiceConfig)| {
+            let mut new = old.clone();
+            change(&mut new);
+            updatable_in_place(&old, &new)
+        };
+
+        // Carried by re-announcing the SRV and TXT records
+        assert!(edit(|s| s.port = 9090));
+        assert!(edit(|s| s.txt = vec![TxtEntry::text("version", "2")]));
+        assert!(edit(|s| s.txt_sources = vec![TxtSource {
+            key: "commit".into(),
+            kind: TxtSourceKind::File {
+                path: "/tmp/commit".into(),
+            },
+            interval_secs: 60,
+        }]));
+        assert!(edit(|s| s.conflict_policy = ConflictPolicy::Number));
+        assert!(edit(|s| s.health_check = Some(HealthCheck {
+            probe: HealthProbe::Tcp,
+            interval_secs: 10,
+            failure_threshold: 3,
+            success_threshold: 1,
+        })));
+        assert!(edit(|s| s.retry = Some(RetryPolicy::default())));
+
+        // Change the fullname, PTR records or host, or how the service runs
+        assert!(!edit(|s| s.name = "Api".into()));
+        assert!(!edit(|s| s.service_type = "_ipp._tcp".into()));
+        assert!(!edit(|s| s.subtype = Some("_printer".into())));
+        assert!(!edit(|s| s.hostname = Some("devbox".into())));
+        assert!(!edit(|s| s.proxy = Some(ProxyTarget {
+            host: "printer.local".into(),
+            addresses: vec!["192.168.1.50".parse().unwrap()],
+        })));
+        assert!(!edit(|s| s.interfaces = Some(InterfaceFilter {
+            mode: InterfaceFilterMode::Allow,
+            patterns: vec!["eth*".into()],
+        })));
+        assert!(!edit(|s| s.process = Some(ManagedProcess {
+            command: "serve".into(),
+            args: vec![],
+            cwd: None,
+            env: Default::default(),
+            restart: Default::default(),
+        })));
+        assert!(!edit(
+            |s| s.bound_process = Some(ProcessBinding::Pid { pid: 1 })
+        ));
+        assert!(!edit(|s| s.schedule = Some(Schedule {
+            days: vec![chrono::Weekday::Mon],
+            start: "09:00".into(),
+            end: "17:00".into(),
+        })));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn republishing_edit_keeps_process() {
+        let daemon = match mdns::create_daemon() {
+            Ok(d) => d,
+            Err(_) => return, // Skip if daemon creation fails (e.g. network constraints in CI)
+        };
+        let state = AppState::new(AppConfig::default(), daemon.clone());
+        let old = ServiceConfig {
+            id: "web".into(),
+            name: "Web".into(),
+            service_type: "_http._tcp".into(),
+            port: 8080,
+            enabled: true,
+            process: Some(ManagedProcess {
+                command: "sleep".into(),
+                args: vec!["30".into()],
+                cwd: None,
+                env: Default::default(),
+                restart: Default::default(),
+            }),
+            ..Default::default()
+        };
+        let registered = mdns::register_service(&daemon, &old, "testhost", &[]).unwrap();
+        record_published(&state, registered, "testhost", &[]);
+        let child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        {
+            let mut processes = state.processes.lock().unwrap();
+            processes.insert(old.id.clone(), process::RunningProcess::new(child, 0));
+        }
+        {
+            let mut statuses = state.statuses.lock().unwrap();
+            statuses.insert(old.id.clone(), ServiceStatus::Running);
+        }
+
+        // A rename needs a new registration, not a new process
+        let mut renamed = old.clone();
+        renamed.name = "Api".into();
+        assert_eq!(
+            prepare_edit(&state, &old, &renamed, Some(ServiceStatus::Running)),
+            EditPath::Republish
+        );
+        assert!(!state.published.lock().unwrap().contains_key(&old.id));
+        assert_eq!(
+            state.processes.lock().unwrap().get(&old.id).map(|p| p.pid),
+            Some(pid)
+        );
+
+        // A new command does
+        let mut rerun = renamed.clone();
+        rerun.process.as_mut().unwrap().args = vec!["60".into()];
+        assert_eq!(
+            prepare_edit(&state, &renamed, &rerun, Some(ServiceStatus::Running)),
+            EditPath::Restart
+        );
+        assert!(state.processes.lock().unwrap().is_empty());
+        let _ = daemon.shutdown();
+    }
+
+    #[test]
+    fn unregister_withdraws_renamed_service() {
+        let daemon = match mdns::create_daemon() {
+            Ok(d) => d,
+            Err(_) => return, // Skip if daemon creation fails (e.g. network constraints in CI)
+        };
+        let state = AppState::new(AppConfig::default(), daemon.clone());
+        let svc = ServiceConfig {
+            id: "renamed".into(),
+            name: "Renamed Service".into(),
+            service_type: "_http._tcp".into(),
+            port: 8080,
+            enabled: true,
+            ..Default::default()
+        };
+        let registered = mdns::register_service(&daemon, &svc, "testhost", &[]).unwrap();
+        record_published(&state, registered, "testhost", &[]);
+        record_instance_name(&state, &svc.id, "Renamed Service (2)");
+
+        unregister_on_daemon(&state, &svc, true).unwrap();
+
+        let status = daemon
+            .unregister("Renamed Service._http._tcp.local.")
+            .unwrap()
+            .recv_timeout(Duration::from_secs(2))
+            .unwrap();
+        assert!(matches!(status, UnregisterStatus::NotFound));
+        let _ = daemon.shutdown();
+    }
+
+    #[test]
+    fn unregister_and_wait_withdraws_all_services() {
+        let daemon = match mdns::create_daemon() {
+            Ok(d) => d,
+            Err(_) => return, // Skip if daemon creation fails (e.g. network constraints in CI)
+        };
+        let state = AppState::new(AppConfig::default(), daemon.clone());
+        let services: Vec<ServiceConfig> = ["First", "Second"]
+            .into_iter()
+            .map(|name| ServiceConfig {
+                id: name.to_lowercase(),
+                name: name.into(),
+                service_type: "_http._tcp".into(),
+                port: 8080,
+                enabled: true,
+                ..Default::default()
+            })
+            .collect();
+        for svc in &services {
+            let registered = mdns::register_service(&daemon, svc, "testhost", &[]).unwrap();
+            record_published(&state, registered, "testhost", &[]);
+        }
+        let never_published = ServiceConfig {
+            id: "idle".into(),
+            ..Default::default()
+        };
+
+        let mut all = services.clone();
+        all.push(never_published);
+        let results = unregister_and_wait(&state, &all);
+
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(Result::is_ok));
+        assert!(state.published.lock().unwrap().is_empty());
+        let _ = daemon.shutdown();
+    }
+}
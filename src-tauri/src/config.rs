@@ -61,7 +61,10 @@ mod tests {
         let config = AppConfig {
             version: 1,
             hostname: "testhost".into(),
+            hostname_override: Some("devbox".into()),
             services: vec![],
+            aliases: vec![],
+            watchlist: vec![],
         };
 
         let content = serde_json::to_string_pretty(&config).unwrap();
@@ -71,6 +74,7 @@ mod tests {
             serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
         assert_eq!(loaded.version, 1);
         assert_eq!(loaded.hostname, "testhost");
+        assert_eq!(loaded.hostname_override.as_deref(), Some("devbox"));
         assert!(loaded.services.is_empty());
     }
 
@@ -8,6 +8,15 @@ pub enum AppError {
     #[error("mDNS error: {0}")]
     Mdns(String),
 
+    #[error("TXT record error: {0}")]
+    Txt(String),
+
+    #[error("Template error: {0}")]
+    Template(String),
+
+    #[error("Process error: {0}")]
+    Process(String),
+
     #[error("Service not found: {0}")]
     NotFound(String),
 
@@ -43,6 +52,27 @@ mod tests {
         assert_eq!(err.to_string(), "mDNS error: bind failed");
     }
 
+    #[test]
+    fn txt_error_display() {
+        let err = AppError::Txt("duplicate key 'path'".into());
+        assert_eq!(err.to_string(), "TXT record error: duplicate key 'path'");
+    }
+
+    #[test]
+    fn template_error_display() {
+        let err = AppError::Template("environment variable 'TEAM' is not set".into());
+        assert_eq!(
+            err.to_string(),
+            "Template error: environment variable 'TEAM' is not set"
+        );
+    }
+
+    #[test]
+    fn process_error_display() {
+        let err = AppError::Process("'npm' not found".into());
+        assert_eq!(err.to_string(), "Process error: 'npm' not found");
+    }
+
     #[test]
     fn not_found_error_display() {
         let err = AppError::NotFound("abc-123".into());
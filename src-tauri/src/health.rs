@@ -0,0 +1,442 @@
+use crate::commands;
+use crate::error::AppError;
+use crate::logging;
+use crate::models::{HealthCheck, HealthProbe, LogLevel, ServiceConfig, ServiceStatus};
+use crate::state::AppState;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How often the checker wakes up to see which checks are due.
+const TICK: Duration = Duration::from_secs(1);
+
+/// Upper bound for a single connect or HTTP exchange.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Consecutive results for one service's health check.
+#[derive(Debug, Default)]
+pub struct HealthState {
+    last_run: Option<Instant>,
+    /// A probe is running, so no other one is started.
+    probing: bool,
+    failures: u32,
+    successes: u32,
+}
+
+/// Why a probe failed.
+#[derive(Debug)]
+pub struct ProbeError {
+    pub reason: String,
+    /// Every address refused the connection: nothing listens on the port.
+    pub refused: bool,
+}
+
+impl From<String> for ProbeError {
+    fn from(reason: String) -> Self {
+        ProbeError {
+            reason,
+            refused: false,
+        }
+    }
+}
+
+pub fn validate(check: &HealthCheck) -> Result<(), AppError> {
+    if check.interval_secs == 0 {
+        return Err(AppError::Config(
+            "Health check interval must be at least 1 second".into(),
+        ));
+    }
+    if check.failure_threshold == 0 || check.success_threshold == 0 {
+        return Err(AppError::Config(
+            "Health check thresholds must be at least 1".into(),
+        ));
+    }
+    if let HealthProbe::Http {
+        path,
+        expected_status,
+    } = &check.probe
+    {
+        if !path.starts_with('/') || path.contains(char::is_whitespace) {
+            return Err(AppError::Config(format!(
+                "Invalid health check path '{}'",
+                path
+            )));
+        }
+        if !(100..=599).contains(expected_status) {
+            return Err(AppError::Config(format!(
+                "Invalid expected HTTP status {}",
+                expected_status
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Addresses the advertised endpoint is reachable on: the proxy target's
+/// addresses, or this machine.
+fn endpoints(svc: &ServiceConfig) -> Vec<SocketAddr> {
+    match &svc.proxy {
+        Some(proxy) => proxy
+            .addresses
+            .iter()
+            .map(|ip| SocketAddr::new(*ip, svc.port))
+            .collect(),
+        None => ("localhost", svc.port)
+            .to_socket_addrs()
+            .map(|addrs| addrs.collect())
+            .unwrap_or_default(),
+    }
+}
+
+fn connect(svc: &ServiceConfig) -> Result<TcpStream, ProbeError> {
+    let mut error = ProbeError::from(format!("no address to check for port {}", svc.port));
+    let mut refused = true;
+    for addr in endpoints(svc) {
+        match TcpStream::connect_timeout(&addr, PROBE_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => {
+                refused &= e.kind() == ErrorKind::ConnectionRefused;
+                error = ProbeError {
+                    reason: format!("connect to {} failed: {}", addr, e),
+                    refused,
+                };
+            }
+        }
+    }
+    Err(error)
+}
+
+/// Status code from an HTTP status line such as `HTTP/1.1 200 OK`.
+fn parse_status_line(line: &str) -> Option<u16> {
+    let mut parts = line.split_whitespace();
+    parts.next().filter(|v| v.starts_with("HTTP/"))?;
+    parts.next()?.parse().ok()
+}
+
+fn http_get(mut stream: TcpStream, path: &str) -> Result<u16, String> {
+    stream
+        .set_read_timeout(Some(PROBE_TIMEOUT))
+        .and_then(|()| stream.set_write_timeout(Some(PROBE_TIMEOUT)))
+        .map_err(|e| e.to_string())?;
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: localhost\r\nUser-Agent: noroshi\r\nConnection: close\r\n\r\n",
+        path
+    );
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| format!("request failed: {}", e))?;
+
+    // Only the status line matters
+    let mut buf = [0u8; 256];
+    let mut response = Vec::new();
+    while !response.contains(&b'\n') && response.len() < 1024 {
+        match stream.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => response.extend_from_slice(&buf[..n]),
+            Err(e) => return Err(format!("no response: {}", e)),
+        }
+    }
+    let text = String::from_utf8_lossy(&response);
+    parse_status_line(text.lines().next().unwrap_or(""))
+        .ok_or_else(|| "malformed HTTP response".to_string())
+}
+
+/// Run the service's probe once.
+pub fn probe(svc: &ServiceConfig, check: &HealthCheck) -> Result<(), ProbeError> {
+    let stream = connect(svc)?;
+    match &check.probe {
+        HealthProbe::Tcp => Ok(()),
+        HealthProbe::Http {
+            path,
+            expected_status,
+        } => {
+            let status = http_get(stream, path)?;
+            if status == *expected_status {
+                Ok(())
+            } else {
+                Err(format!(
+                    "GET {} returned {} instead of {}",
+                    path, status, expected_status
+                )
+                .into())
+            }
+        }
+    }
+}
+
+/// Run due health checks in the background until the app shuts down.
+pub fn spawn_checker(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        let state = app.state::<AppState>();
+        if *state.shut_down.lock().unwrap() {
+            break;
+        }
+        run_due_checks(&app, &state);
+    });
+}
+
+/// Whether `svc` is held back until its health check passes: it has a
+/// check, and that check has not passed since the service was started.
+pub fn must_wait(state: &AppState, svc: &ServiceConfig) -> bool {
+    let Some(check) = &svc.health_check else {
+        return false;
+    };
+    let health = state.health.lock().unwrap();
+    health
+        .get(&svc.id)
+        .is_none_or(|h| h.successes < check.success_threshold)
+}
+
+/// Forget the results of a stopped service, so that it waits for its check
+/// again when it is started.
+pub fn forget(state: &AppState, id: &str) {
+    let mut health = state.health.lock().unwrap();
+    health.remove(id);
+}
+
+/// Start the probes that are due, each on its own thread so that a slow
+/// endpoint does not hold up the others.
+fn run_due_checks(app: &AppHandle, state: &AppState) {
+    let candidates: Vec<ServiceConfig> = {
+        let config = state.config.lock().unwrap();
+        let statuses = state.statuses.lock().unwrap();
+        config
+            .services
+            .iter()
+            .filter(|s| s.health_check.is_some())
+            .filter(|s| statuses.get(&s.id).is_some_and(|st| st.is_active()))
+            .cloned()
+            .collect()
+    };
+
+    let due: Vec<ServiceConfig> = {
+        let mut health = state.health.lock().unwrap();
+        // Forget services that were stopped or lost their check
+        health.retain(|id, _| candidates.iter().any(|s| &s.id == id));
+        candidates
+            .into_iter()
+            .filter(|svc| {
+                let Some(check) = &svc.health_check else {
+                    return false;
+                };
+                let entry = health.entry(svc.id.clone()).or_default();
+                let due = !entry.probing
+                    && entry.last_run.is_none_or(|last| {
+                        last.elapsed() >= Duration::from_secs(check.interval_secs)
+                    });
+                if due {
+                    entry.probing = true;
+                    entry.last_run = Some(Instant::now());
+                }
+                due
+            })
+            .collect()
+    };
+
+    for svc in due {
+        let app = app.clone();
+        thread::spawn(move || {
+            let Some(check) = &svc.health_check else {
+                return;
+            };
+            let result = probe(&svc, check);
+            let state = app.state::<AppState>();
+            if apply_result(&app, &state, &svc, check, result) {
+                if let Ok(views) = commands::build_views(&state) {
+                    let _ = app.emit("services-changed", &views);
+                }
+            }
+        });
+    }
+}
+
+/// Count a probe result and publish or withdraw the service when it crosses
+/// a threshold. A refused connection withdraws it right away, since nothing
+/// is listening to be advertised. Returns whether its status changed.
+fn apply_result(
+    app: &AppHandle,
+    state: &AppState,
+    svc: &ServiceConfig,
+    check: &HealthCheck,
+    result: Result<(), ProbeError>,
+) -> bool {
+    let (failures, successes) = {
+        let mut health = state.health.lock().unwrap();
+        // Stopped while the probe ran
+        let Some(entry) = health.get_mut(&svc.id) else {
+            return false;
+        };
+        entry.probing = false;
+        if result.is_ok() {
+            entry.successes += 1;
+            entry.failures = 0;
+        } else {
+            entry.failures += 1;
+            entry.successes = 0;
+        }
+        (entry.failures, entry.successes)
+    };
+    let status = {
+        let statuses = state.statuses.lock().unwrap();
+        statuses.get(&svc.id).copied()
+    };
+    let hostname = {
+        let config = state.config.lock().unwrap();
+        config.effective_hostname().to_string()
+    };
+
+    match (status, result) {
+        (Some(ServiceStatus::Running), Err(e))
+            if e.refused || failures >= check.failure_threshold =>
+        {
+            let _ = commands::unregister_on_daemon(state, svc, false);
+            {
+                let mut statuses = state.statuses.lock().unwrap();
+                statuses.insert(svc.id.clone(), ServiceStatus::Unhealthy);
+            }
+            logging::append_log(
+                app,
+                state,
+                LogLevel::Warn,
+                format!(
+                    "Service '{}' is unhealthy ({}), advertisement withdrawn",
+                    svc.name, e.reason
+                ),
+                Some(svc.id.clone()),
+            );
+            true
+        }
+        // Only when the threshold is first reached; a service that passes
+        // but waits for its bound process is published by the binding watcher
+        (Some(ServiceStatus::Waiting), Ok(())) if successes == check.success_threshold => {
+            logging::append_log(
+                app,
+                state,
+                LogLevel::Info,
+                format!("Service '{}' passed its health check", svc.name),
+                Some(svc.id.clone()),
+            );
+            commands::try_register_service(app, state, svc, &hostname);
+            true
+        }
+        (Some(ServiceStatus::Unhealthy), Ok(())) if successes >= check.success_threshold => {
+            logging::append_log(
+                app,
+                state,
+                LogLevel::Info,
+                format!("Service '{}' is healthy again", svc.name),
+                Some(svc.id.clone()),
+            );
+            commands::try_register_service(app, state, svc, &hostname);
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(probe: HealthProbe) -> HealthCheck {
+        HealthCheck {
+            probe,
+            interval_secs: 10,
+            failure_threshold: 3,
+            success_threshold: 1,
+        }
+    }
+
+    #[test]
+    fn validate_rejects_bad_settings() {
+        assert!(validate(&check(HealthProbe::Tcp)).is_ok());
+        assert!(validate(&HealthCheck {
+            interval_secs: 0,
+            ..check(HealthProbe::Tcp)
+        })
+        .is_err());
+        assert!(validate(&HealthCheck {
+            failure_threshold: 0,
+            ..check(HealthProbe::Tcp)
+        })
+        .is_err());
+        assert!(validate(&check(HealthProbe::Http {
+            path: "health".into(),
+            expected_status: 200
+        }))
+        .is_err());
+        assert!(validate(&check(HealthProbe::Http {
+            path: "/health".into(),
+            expected_status: 99
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn parse_status_line_reads_code() {
+        assert_eq!(parse_status_line("HTTP/1.1 204 No Content"), Some(204));
+        assert_eq!(parse_status_line("HTTP/1.0 500"), Some(500));
+        assert_eq!(parse_status_line("SSH-2.0-OpenSSH"), None);
+    }
+
+    #[test]
+    fn probe_checks_local_listener() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let svc = ServiceConfig {
+            id: "test-id".into(),
+            name: "Web".into(),
+            service_type: "_http._tcp".into(),
+            port,
+            enabled: true,
+            proxy: Some(crate::models::ProxyTarget {
+                host: "localhost".into(),
+                addresses: vec!["127.0.0.1".parse().unwrap()],
+            }),
+            ..Default::default()
+        };
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 512];
+            let _ = stream.read(&mut buf);
+            stream
+                .write_all(b"HTTP/1.1 503 Service Unavailable\r\n\r\n")
+                .unwrap();
+        });
+        let result = probe(
+            &svc,
+            &check(HealthProbe::Http {
+                path: "/health".into(),
+                expected_status: 200,
+            }),
+        );
+        server.join().unwrap();
+        assert!(result.unwrap_err().reason.contains("503"));
+    }
+
+    #[test]
+    fn probe_reports_closed_port_as_refused() {
+        let port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let svc = ServiceConfig {
+            id: "test-id".into(),
+            name: "Web".into(),
+            service_type: "_http._tcp".into(),
+            port,
+            enabled: true,
+            proxy: Some(crate::models::ProxyTarget {
+                host: "localhost".into(),
+                addresses: vec!["127.0.0.1".parse().unwrap()],
+            }),
+            ..Default::default()
+        };
+        assert!(probe(&svc, &check(HealthProbe::Tcp)).unwrap_err().refused);
+    }
+}
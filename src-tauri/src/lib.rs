@@ -1,21 +1,31 @@
+mod binding;
+mod browse;
 mod commands;
 mod config;
 mod error;
+mod health;
 mod logging;
 mod mdns;
 mod models;
+mod monitor;
 mod network;
+mod process;
+mod reconcile;
+mod schedule;
 mod state;
+mod template;
+mod txt;
+mod txt_source;
+mod verify;
+mod watch;
 
 use commands::*;
 use models::{LogLevel, ServiceStatus};
 use state::AppState;
-use std::collections::{HashMap, VecDeque};
-use std::sync::Mutex;
 use tauri::image::Image;
 use tauri::menu::{MenuBuilder, MenuItem};
 use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Manager, RunEvent};
 
 fn show_main_window(app: &AppHandle) {
     if let Some(window) = app.get_webview_window("main") {
@@ -36,7 +46,7 @@ fn show_main_window(app: &AppHandle) {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let cfg = match config::load_config() {
+    let mut cfg = match config::load_config() {
         Ok(cfg) => cfg,
         Err(e) => {
             eprintln!("Failed to load config: {}", e);
@@ -51,22 +61,28 @@ pub fn run() {
         }
     };
 
-    let app_state = AppState {
-        config: Mutex::new(cfg),
-        daemon: Mutex::new(daemon),
-        statuses: Mutex::new(HashMap::new()),
-        logs: Mutex::new(VecDeque::new()),
-    };
+    if schedule::disable_expired(&mut cfg, chrono::Utc::now()) {
+        let _ = config::save_config(&cfg);
+    }
+
+    let app_state = AppState::new(cfg, daemon);
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
         .manage(app_state)
         .setup(|app| {
             let state = app.state::<AppState>();
             let handle = app.handle();
 
+            {
+                let daemon = state.daemon.lock().unwrap();
+                monitor::watch_daemon(handle, &daemon);
+            }
+
             // Auto-start enabled services
             let services: Vec<models::ServiceConfig>;
+            let aliases: Vec<models::HostAlias>;
             let hostname: String;
             {
                 let config = state.config.lock().unwrap();
@@ -76,17 +92,33 @@ pub fn run() {
                     .filter(|s| s.enabled)
                     .cloned()
                     .collect();
-                hostname = config.hostname.clone();
+                aliases = config
+                    .aliases
+                    .iter()
+                    .filter(|a| a.enabled)
+                    .cloned()
+                    .collect();
+                hostname = config.effective_hostname().to_string();
             }
             for svc in &services {
-                commands::try_register_service(handle, &state, svc, &hostname);
+                reconcile::converge(handle, &state, svc, &hostname);
+            }
+            for alias in &aliases {
+                commands::try_register_alias(handle, &state, alias);
             }
 
+            watch::sync(handle, &state);
+            health::spawn_checker(handle);
+            binding::spawn_watcher(handle);
+            schedule::spawn_scheduler(handle);
+            txt_source::spawn_refresher(handle);
+            reconcile::spawn_reconciler(handle);
+
             let enabled_count = {
                 let statuses = state.statuses.lock().unwrap();
-                statuses
-                    .values()
-                    .filter(|s| **s == ServiceStatus::Running)
+                services
+                    .iter()
+                    .filter(|s| statuses.get(&s.id).copied() == Some(ServiceStatus::Running))
                     .count()
             };
             logging::append_log(
@@ -101,6 +133,19 @@ pub fn run() {
                 None,
             );
 
+            // Route SIGTERM/SIGINT through the regular exit path so services
+            // are withdrawn before the process goes away
+            let signal_handle = handle.clone();
+            if let Err(e) = ctrlc::set_handler(move || signal_handle.exit(0)) {
+                logging::append_log(
+                    handle,
+                    &state,
+                    LogLevel::Warn,
+                    format!("Failed to install signal handler: {}", e),
+                    None,
+                );
+            }
+
             // Build system tray menu
             let show_item = MenuItem::with_id(app, "show", "ウィンドウを表示", true, None::<&str>)?;
             let quit_item = MenuItem::with_id(app, "quit", "終了", true, None::<&str>)?;
@@ -145,22 +190,44 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             get_services,
+            get_published_records,
             add_service,
             update_service,
             delete_service,
             toggle_service,
+            start_service_for,
             start_all,
             stop_all,
+            reconcile_services,
+            get_aliases,
+            add_alias,
+            delete_alias,
+            toggle_alias,
             get_host_name,
+            get_host_info,
+            set_hostname_override,
             get_event_logs,
             clear_event_logs,
             get_network_interfaces,
+            start_browse,
+            stop_browse,
+            get_service_types,
+            stop_service_types,
+            clone_discovered_service,
+            get_watchlist,
+            add_watch,
+            delete_watch,
             export_config,
             import_config,
         ])
-        .run(tauri::generate_context!())
+        .build(tauri::generate_context!())
         .unwrap_or_else(|e| {
             eprintln!("Error while running tauri application: {}", e);
             std::process::exit(1);
+        })
+        .run(|app, event| {
+            if let RunEvent::Exit = event {
+                commands::shutdown_services(app, &app.state::<AppState>());
+            }
         });
 }
@@ -1,13 +1,40 @@
 use crate::error::AppError;
-use crate::models::ServiceConfig;
-use mdns_sd::{ServiceDaemon, ServiceInfo};
+use crate::models::{HostAlias, ProxyTarget, ServiceConfig};
+use crate::template;
+use crate::txt;
+use mdns_sd::{DaemonStatus, IfKind, Receiver, ServiceDaemon, ServiceInfo, UnregisterStatus};
+use std::time::{Duration, Instant};
+
+/// How long to wait for the daemon to confirm that goodbye packets went out.
+const GOODBYE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long to wait for the daemon thread to acknowledge a shutdown request.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// mdns-sd only publishes address records as part of a service, so host
+/// aliases ride on a placeholder service of this type. It is our own type
+/// so that no other application mistakes the placeholder for a real
+/// service, and the Browse tab hides these instances, see
+/// [`alias_fullname`].
+const ALIAS_SERVICE_TYPE: &str = "_noroshi-alias._tcp.local.";
 
 pub fn create_daemon() -> Result<ServiceDaemon, AppError> {
     ServiceDaemon::new().map_err(|e| AppError::Mdns(e.to_string()))
 }
 
-fn to_mdns_type(service_type: &str) -> String {
-    let t = service_type.trim_end_matches('.');
+/// Split an optional subtype off a service type (`_printer._sub._http._tcp`).
+fn split_subtype(service_type: &str) -> (Option<&str>, &str) {
+    match service_type.split_once("._sub.") {
+        Some((sub, base)) => (Some(sub), base),
+        None => (None, service_type),
+    }
+}
+
+/// Fully qualified base service type, e.g. `_http._tcp.local.`. Any subtype
+/// prefix is dropped, since instance fullnames never include it.
+pub fn to_mdns_type(service_type: &str) -> String {
+    let (_, base) = split_subtype(service_type);
+    let t = base.trim_end_matches('.');
     if t.ends_with(".local") {
         format!("{}.", t)
     } else {
@@ -15,87 +42,364 @@ fn to_mdns_type(service_type: &str) -> String {
     }
 }
 
-fn normalize_hostname(hostname: &str) -> String {
-    if hostname.ends_with(".local.") {
-        hostname.to_string()
-    } else if hostname.ends_with(".local") {
-        format!("{}.", hostname)
-    } else {
-        format!("{}.local.", hostname)
+/// Instance fullname as announced, e.g. `My Service._http._tcp.local.`.
+pub fn service_fullname(config: &ServiceConfig) -> String {
+    format!("{}.{}", config.name, to_mdns_type(&config.service_type))
+}
+
+/// Split an instance fullname into its instance name and base service type,
+/// e.g. `My Service._http._tcp.local.` into `My Service` and `_http._tcp`.
+pub fn split_fullname(fullname: &str) -> Option<(String, String)> {
+    let trimmed = fullname.trim_end_matches('.');
+    let rest = trimmed.strip_suffix(".local")?;
+    let (rest, proto) = rest.rsplit_once('.')?;
+    let (instance, service) = rest.rsplit_once('.')?;
+    if instance.is_empty() || !service.starts_with('_') || !proto.starts_with('_') {
+        return None;
+    }
+    Some((instance.to_string(), format!("{}.{}", service, proto)))
+}
+
+/// Instance name part of `fullname` if it belongs to `service_type`.
+pub fn instance_name_of(fullname: &str, service_type: &str) -> Option<String> {
+    let suffix = format!(".{}", to_mdns_type(service_type));
+    let lower = fullname.to_ascii_lowercase();
+    lower
+        .ends_with(&suffix.to_ascii_lowercase())
+        .then(|| fullname[..fullname.len() - suffix.len()].to_string())
+}
+
+pub fn validate_subtype(subtype: &str) -> Result<(), AppError> {
+    let label = subtype.trim();
+    if label.is_empty()
+        || label.len() > 63
+        || label.contains('.')
+        || label.contains(char::is_whitespace)
+    {
+        return Err(AppError::Config(format!("Invalid subtype '{}'", subtype)));
+    }
+    Ok(())
+}
+
+/// Fully qualified type to browse for. A subtype is kept, so that only
+/// instances registered under it are found (`_printer._sub._http._tcp.local.`).
+pub fn to_browse_type(service_type: &str) -> Result<String, AppError> {
+    let (subtype, _) = split_subtype(service_type);
+    let mdns_type = to_mdns_type(service_type);
+    match subtype {
+        Some(sub) => {
+            validate_subtype(sub)?;
+            Ok(format!("{}._sub.{}", sub, mdns_type))
+        }
+        None => Ok(mdns_type),
     }
 }
 
+/// Service type passed to `ServiceInfo`, including the subtype PTR domain
+/// when one is configured (`_printer._sub._http._tcp.local.`).
+fn registration_type(config: &ServiceConfig) -> Result<String, AppError> {
+    let (embedded, _) = split_subtype(&config.service_type);
+    let subtype = match (config.subtype.as_deref().map(str::trim), embedded) {
+        // mdns-sd keys registrations by instance fullname, so a second
+        // ServiceInfo with another subtype would replace the first
+        (Some(configured), Some(embedded)) if configured != embedded => {
+            return Err(AppError::Config(
+                "Only one subtype per service can be published".into(),
+            ));
+        }
+        (configured, embedded) => configured.or(embedded),
+    };
+
+    let mdns_type = to_mdns_type(&config.service_type);
+    match subtype {
+        Some(sub) => {
+            validate_subtype(sub)?;
+            Ok(format!("{}._sub.{}", sub, mdns_type))
+        }
+        None => Ok(mdns_type),
+    }
+}
+
+/// Validate a `.local` hostname and return it fully qualified (`name.local.`).
+pub fn normalize_hostname(hostname: &str) -> Result<String, AppError> {
+    let invalid =
+        |reason: &str| AppError::Config(format!("Invalid hostname '{}': {}", hostname, reason));
+
+    let trimmed = hostname.trim().trim_end_matches('.');
+    let name = trimmed.strip_suffix(".local").unwrap_or(trimmed);
+    if name.is_empty() {
+        return Err(invalid("name is empty"));
+    }
+    if name.len() > 249 {
+        return Err(invalid("name is too long"));
+    }
+    for label in name.split('.') {
+        if label.is_empty() {
+            return Err(invalid("empty label"));
+        }
+        if label.len() > 63 {
+            return Err(invalid("label longer than 63 bytes"));
+        }
+        if label.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(invalid("contains whitespace"));
+        }
+    }
+    Ok(format!("{}.local.", name))
+}
+
+/// Hostname the SRV record points at: the proxied target if any, then the
+/// service's own override, then the global hostname.
+pub fn service_host(config: &ServiceConfig, hostname: &str) -> Result<String, AppError> {
+    if let Some(proxy) = &config.proxy {
+        return normalize_hostname(&proxy.host);
+    }
+    match config.hostname.as_deref() {
+        Some(own) if !own.trim().is_empty() => normalize_hostname(own),
+        _ => normalize_hostname(hostname),
+    }
+}
+
+pub fn validate_proxy(proxy: &ProxyTarget) -> Result<(), AppError> {
+    let host = proxy.host.trim();
+    if host.is_empty() {
+        return Err(AppError::Config("Proxy target hostname is empty".into()));
+    }
+    if host.contains(char::is_whitespace) {
+        return Err(AppError::Config(format!(
+            "Invalid proxy target hostname '{}'",
+            host
+        )));
+    }
+    if proxy.addresses.is_empty() {
+        return Err(AppError::Config(format!(
+            "Proxy target '{}' needs at least one address",
+            host
+        )));
+    }
+    Ok(())
+}
+
+/// Publish `config` with its templates expanded, announcing only on the
+/// named `interfaces` unless that list is empty. Returns the config as
+/// registered.
 pub fn register_service(
     daemon: &ServiceDaemon,
     config: &ServiceConfig,
     hostname: &str,
-) -> Result<(), AppError> {
-    let mdns_type = to_mdns_type(&config.service_type);
-    let instance_name = &config.name;
-
-    let host = normalize_hostname(hostname);
-
-    let properties: Vec<(&str, &str)> = config
-        .txt
-        .iter()
-        .map(|(k, v)| (k.as_str(), v.as_str()))
-        .collect();
-
-    let service = ServiceInfo::new(
-        &mdns_type,
-        instance_name,
-        &host,
-        "",
-        config.port,
-        &properties[..],
-    )
-    .map_err(|e| AppError::Mdns(e.to_string()))?
-    .enable_addr_auto();
-
+    interfaces: &[String],
+) -> Result<ServiceConfig, AppError> {
+    let rendered = template::render_service(config, hostname)?;
+    let service = service_info(&rendered, hostname, interfaces)?;
     daemon
         .register(service)
         .map_err(|e| AppError::Mdns(e.to_string()))?;
+    Ok(rendered)
+}
 
-    Ok(())
+/// Whether a running registration of `old` can take the edits in `new` by
+/// re-announcing its records. Anything that changes the instance fullname,
+/// the PTR records or the host it points at needs a full unregister and
+/// register instead. An in-place update sends no goodbye for the records it
+/// replaces, see [`update_service`].
+pub fn can_update_in_place(old: &ServiceConfig, new: &ServiceConfig) -> bool {
+    old.name == new.name
+        && old.service_type == new.service_type
+        && old.subtype == new.subtype
+        && old.hostname == new.hostname
+        && old.proxy == new.proxy
+        && old.interfaces == new.interfaces
 }
 
-pub fn unregister_service(
+/// Whether the TXT or SRV records differ between two rendered configs.
+fn records_changed(old: &ServiceConfig, new: &ServiceConfig) -> bool {
+    old.port != new.port || old.txt != new.txt
+}
+
+/// Re-announce the records of a running registration with the values of
+/// `config`, under the instance name `registered` was registered as. mdns-sd
+/// replaces a registration with the same fullname and announces it with the
+/// cache-flush bit set, so browsers swap the TXT and SRV records without
+/// the instance disappearing; a name it picked after a conflict is kept.
+/// Nothing is sent when no record changed. Returns the config as registered.
+///
+/// The old SRV and TXT records get no goodbye. A browser that misses the
+/// announcement, or ignores the cache-flush bit, keeps them until their TTL
+/// runs out.
+pub fn update_service(
     daemon: &ServiceDaemon,
+    registered: &ServiceConfig,
     config: &ServiceConfig,
     hostname: &str,
-) -> Result<(), AppError> {
-    let mdns_type = to_mdns_type(&config.service_type);
+    interfaces: &[String],
+) -> Result<ServiceConfig, AppError> {
+    let mut rendered = template::render_service(config, hostname)?;
+    rendered.name.clone_from(&registered.name);
+    if records_changed(registered, &rendered) {
+        daemon
+            .register(service_info(&rendered, hostname, interfaces)?)
+            .map_err(|e| AppError::Mdns(e.to_string()))?;
+    }
+    Ok(rendered)
+}
 
-    let host = normalize_hostname(hostname);
+/// Build the `ServiceInfo` for an already rendered config.
+fn service_info(
+    config: &ServiceConfig,
+    hostname: &str,
+    interfaces: &[String],
+) -> Result<ServiceInfo, AppError> {
+    let mdns_type = registration_type(config)?;
+    let instance_name = &config.name;
 
-    let fullname = format!("{}.{}", config.name, mdns_type);
+    let host = service_host(config, hostname)?;
 
-    let properties: Vec<(&str, &str)> = config
-        .txt
-        .iter()
-        .map(|(k, v)| (k.as_str(), v.as_str()))
-        .collect();
+    let properties = txt::to_properties(&config.txt)?;
 
-    let service = ServiceInfo::new(
-        &mdns_type,
-        &config.name,
-        &host,
-        "",
-        config.port,
-        &properties[..],
-    )
-    .map_err(|e| AppError::Mdns(e.to_string()))?;
+    let mut service = match &config.proxy {
+        Some(proxy) => {
+            validate_proxy(proxy)?;
+            ServiceInfo::new(
+                &mdns_type,
+                instance_name,
+                &host,
+                &proxy.addresses[..],
+                config.port,
+                properties,
+            )
+            .map_err(|e| AppError::Mdns(e.to_string()))?
+        }
+        None => ServiceInfo::new(
+            &mdns_type,
+            instance_name,
+            &host,
+            "",
+            config.port,
+            properties,
+        )
+        .map_err(|e| AppError::Mdns(e.to_string()))?
+        .enable_addr_auto(),
+    };
+    if !interfaces.is_empty() {
+        service.set_interfaces(interfaces.iter().cloned().map(IfKind::Name).collect());
+    }
+    Ok(service)
+}
 
+/// Unregister the instance published as `fullname`. The returned receiver
+/// reports once the daemon has sent its goodbye records, see
+/// [`wait_for_goodbye`].
+pub fn unregister_service(
+    daemon: &ServiceDaemon,
+    fullname: &str,
+) -> Result<Receiver<UnregisterStatus>, AppError> {
     daemon
-        .unregister(&fullname)
-        .map_err(|e| AppError::Mdns(e.to_string()))?;
+        .unregister(fullname)
+        .map_err(|e| AppError::Mdns(e.to_string()))
+}
+
+pub fn validate_alias_name(name: &str) -> Result<(), AppError> {
+    let host = normalize_hostname(name)?;
+    let valid = host.trim_end_matches(".local.").split('.').all(|label| {
+        label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+    });
+    if !valid {
+        return Err(AppError::Config(format!("Invalid alias name '{}'", name)));
+    }
+    Ok(())
+}
 
-    // Drop the receiver to avoid blocking
-    drop(service);
+fn alias_instance_name(alias: &HostAlias) -> Result<String, AppError> {
+    let host = normalize_hostname(&alias.name)?;
+    Ok(host.trim_end_matches(".local.").to_string())
+}
 
+/// Publish A/AAAA records for `alias.name`, using this machine's addresses
+/// unless the alias lists its own.
+pub fn register_alias(daemon: &ServiceDaemon, alias: &HostAlias) -> Result<(), AppError> {
+    validate_alias_name(&alias.name)?;
+    let host = normalize_hostname(&alias.name)?;
+    let instance_name = alias_instance_name(alias)?;
+
+    let service = if alias.addresses.is_empty() {
+        ServiceInfo::new(ALIAS_SERVICE_TYPE, &instance_name, &host, "", 0, None)
+            .map_err(|e| AppError::Mdns(e.to_string()))?
+            .enable_addr_auto()
+    } else {
+        ServiceInfo::new(
+            ALIAS_SERVICE_TYPE,
+            &instance_name,
+            &host,
+            &alias.addresses[..],
+            0,
+            None,
+        )
+        .map_err(|e| AppError::Mdns(e.to_string()))?
+    };
+
+    daemon
+        .register(service)
+        .map_err(|e| AppError::Mdns(e.to_string()))
+}
+
+/// Fullname of the placeholder instance `alias` is published under.
+pub fn alias_fullname(alias: &HostAlias) -> Result<String, AppError> {
+    Ok(format!(
+        "{}.{}",
+        alias_instance_name(alias)?,
+        ALIAS_SERVICE_TYPE
+    ))
+}
+
+pub fn unregister_alias(daemon: &ServiceDaemon, alias: &HostAlias) -> Result<(), AppError> {
+    daemon
+        .unregister(&alias_fullname(alias)?)
+        .map_err(|e| AppError::Mdns(e.to_string()))?;
     Ok(())
 }
 
+/// Deadline for goodbye confirmations of unregisters sent from now on.
+pub fn goodbye_deadline() -> Instant {
+    Instant::now() + GOODBYE_TIMEOUT
+}
+
+/// Block until the daemon confirms it sent the goodbye (TTL=0) records for
+/// `fullname`, so peers evict it from their caches right away. Several
+/// unregisters can share one `deadline` to be waited on together.
+pub fn wait_for_goodbye(
+    receiver: &Receiver<UnregisterStatus>,
+    fullname: &str,
+    deadline: Instant,
+) -> Result<(), AppError> {
+    match receiver.recv_deadline(deadline) {
+        Ok(UnregisterStatus::OK) => Ok(()),
+        Ok(UnregisterStatus::NotFound) => {
+            Err(AppError::Mdns(format!("'{}' was not registered", fullname)))
+        }
+        Err(e) => Err(AppError::Mdns(format!(
+            "No goodbye confirmation for '{}': {}",
+            fullname, e
+        ))),
+    }
+}
+
+/// Stop the daemon thread and close its sockets.
+pub fn shutdown_daemon(daemon: &ServiceDaemon) -> Result<(), AppError> {
+    let receiver = daemon
+        .shutdown()
+        .map_err(|e| AppError::Mdns(e.to_string()))?;
+
+    match receiver.recv_timeout(SHUTDOWN_TIMEOUT) {
+        Ok(DaemonStatus::Shutdown) => Ok(()),
+        Ok(status) => Err(AppError::Mdns(format!(
+            "Unexpected daemon status after shutdown: {:?}",
+            status
+        ))),
+        Err(e) => Err(AppError::Mdns(e.to_string())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,19 +424,209 @@ mod tests {
         assert_eq!(to_mdns_type("_ssh._tcp."), "_ssh._tcp.local.");
     }
 
+    #[test]
+    fn to_mdns_type_drops_subtype() {
+        assert_eq!(
+            to_mdns_type("_printer._sub._http._tcp"),
+            "_http._tcp.local."
+        );
+    }
+
+    #[test]
+    fn split_fullname_separates_instance_and_type() {
+        assert_eq!(
+            split_fullname("Build Cache v2.1._http._tcp.local."),
+            Some(("Build Cache v2.1".into(), "_http._tcp".into()))
+        );
+        assert_eq!(split_fullname("_http._tcp.local."), None);
+        assert_eq!(split_fullname("Printer._ipp._tcp.example.com."), None);
+    }
+
+    #[test]
+    fn instance_name_of_strips_type() {
+        assert_eq!(
+            instance_name_of("My Service (2)._http._tcp.local.", "_http._tcp").as_deref(),
+            Some("My Service (2)")
+        );
+        assert_eq!(
+            instance_name_of("Other._ssh._tcp.local.", "_http._tcp"),
+            None
+        );
+    }
+
+    fn subtype_config(service_type: &str, subtype: Option<&str>) -> ServiceConfig {
+        ServiceConfig {
+            id: "test-id".into(),
+            name: "Test".into(),
+            service_type: service_type.into(),
+            port: 8080,
+            enabled: true,
+            subtype: subtype.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn registration_type_without_subtype() {
+        let config = subtype_config("_http._tcp", None);
+        assert_eq!(registration_type(&config).unwrap(), "_http._tcp.local.");
+        assert_eq!(service_fullname(&config), "Test._http._tcp.local.");
+    }
+
+    #[test]
+    fn registration_type_with_subtype() {
+        let config = subtype_config("_http._tcp", Some("_printer"));
+        assert_eq!(
+            registration_type(&config).unwrap(),
+            "_printer._sub._http._tcp.local."
+        );
+    }
+
+    #[test]
+    fn registration_type_with_embedded_subtype() {
+        let config = subtype_config("_printer._sub._http._tcp", Some("_printer"));
+        assert_eq!(
+            registration_type(&config).unwrap(),
+            "_printer._sub._http._tcp.local."
+        );
+        let config = subtype_config("_printer._sub._http._tcp", None);
+        assert_eq!(
+            registration_type(&config).unwrap(),
+            "_printer._sub._http._tcp.local."
+        );
+        let config = subtype_config("_printer._sub._http._tcp", Some("_scanner"));
+        assert!(registration_type(&config).is_err());
+    }
+
+    #[test]
+    fn validate_subtype_rejects_invalid() {
+        assert!(validate_subtype("").is_err());
+        assert!(validate_subtype("_a.b").is_err());
+        assert!(validate_subtype("_a b").is_err());
+        assert!(validate_subtype("_printer").is_ok());
+    }
+
     #[test]
     fn normalize_hostname_plain() {
-        assert_eq!(normalize_hostname("myhost"), "myhost.local.");
+        assert_eq!(normalize_hostname("myhost").unwrap(), "myhost.local.");
     }
 
     #[test]
     fn normalize_hostname_with_local() {
-        assert_eq!(normalize_hostname("myhost.local"), "myhost.local.");
+        assert_eq!(normalize_hostname("myhost.local").unwrap(), "myhost.local.");
     }
 
     #[test]
     fn normalize_hostname_with_local_dot() {
-        assert_eq!(normalize_hostname("myhost.local."), "myhost.local.");
+        assert_eq!(
+            normalize_hostname("myhost.local.").unwrap(),
+            "myhost.local."
+        );
+    }
+
+    #[test]
+    fn normalize_hostname_rejects_invalid() {
+        assert!(normalize_hostname("").is_err());
+        assert!(normalize_hostname(".local").is_err());
+        assert!(normalize_hostname("dev box").is_err());
+        assert!(normalize_hostname("a..b").is_err());
+        assert!(normalize_hostname(&"x".repeat(64)).is_err());
+    }
+
+    fn proxy(host: &str, addresses: &[&str]) -> ProxyTarget {
+        ProxyTarget {
+            host: host.into(),
+            addresses: addresses.iter().map(|a| a.parse().unwrap()).collect(),
+        }
+    }
+
+    #[test]
+    fn validate_proxy_accepts_host_with_address() {
+        assert!(validate_proxy(&proxy("printer", &["192.168.1.50"])).is_ok());
+    }
+
+    #[test]
+    fn validate_proxy_rejects_missing_parts() {
+        assert!(validate_proxy(&proxy("", &["192.168.1.50"])).is_err());
+        assert!(validate_proxy(&proxy("my printer", &["192.168.1.50"])).is_err());
+        assert!(validate_proxy(&proxy("printer", &[])).is_err());
+    }
+
+    #[test]
+    fn service_host_uses_proxy_target() {
+        let mut config = ServiceConfig {
+            id: "test-id".into(),
+            name: "Printer".into(),
+            service_type: "_ipp._tcp".into(),
+            port: 631,
+            enabled: true,
+            ..Default::default()
+        };
+        assert_eq!(service_host(&config, "myhost").unwrap(), "myhost.local.");
+        config.hostname = Some("devbox".into());
+        assert_eq!(service_host(&config, "myhost").unwrap(), "devbox.local.");
+        config.proxy = Some(proxy("printer.local", &["192.168.1.50"]));
+        assert_eq!(service_host(&config, "myhost").unwrap(), "printer.local.");
+    }
+
+    #[test]
+    fn in_place_updates_keep_name_type_and_host() {
+        let old = subtype_config("_http._tcp", None);
+        let mut new = old.clone();
+        new.port = 9090;
+        new.txt = vec![crate::models::TxtEntry {
+            key: "version".into(),
+            value: Some("2".into()),
+            binary: false,
+        }];
+        assert!(can_update_in_place(&old, &new));
+        assert!(records_changed(&old, &new));
+        assert!(!records_changed(&old, &old.clone()));
+
+        new.name = "Renamed".into();
+        assert!(!can_update_in_place(&old, &new));
+        assert!(!can_update_in_place(
+            &old,
+            &subtype_config("_ssh._tcp", None)
+        ));
+        assert!(!can_update_in_place(
+            &old,
+            &subtype_config("_http._tcp", Some("_printer"))
+        ));
+        let mut moved = old.clone();
+        moved.hostname = Some("devbox".into());
+        assert!(!can_update_in_place(&old, &moved));
+    }
+
+    #[test]
+    fn validate_alias_name_accepts_plain_and_local() {
+        assert!(validate_alias_name("api").is_ok());
+        assert!(validate_alias_name("web.local").is_ok());
+        assert!(validate_alias_name("my-box.local.").is_ok());
+    }
+
+    #[test]
+    fn validate_alias_name_rejects_bad_labels() {
+        assert!(validate_alias_name("").is_err());
+        assert!(validate_alias_name(".local").is_err());
+        assert!(validate_alias_name("my api").is_err());
+        assert!(validate_alias_name("-api").is_err());
+        assert!(validate_alias_name("a..b").is_err());
+    }
+
+    #[test]
+    fn alias_instance_name_strips_domain() {
+        let alias = HostAlias {
+            id: "a".into(),
+            name: "api.local".into(),
+            addresses: vec![],
+            enabled: true,
+        };
+        assert_eq!(alias_instance_name(&alias).unwrap(), "api");
+        assert_eq!(
+            alias_fullname(&alias).unwrap(),
+            "api._noroshi-alias._tcp.local."
+        );
     }
 
     #[test]
@@ -146,10 +640,21 @@ mod tests {
             name: "Test".into(),
             service_type: "".into(),
             port: 8080,
-            txt: std::collections::HashMap::new(),
             enabled: true,
+            ..Default::default()
         };
-        let result = register_service(&daemon, &config, "myhost");
+        let result = register_service(&daemon, &config, "myhost", &[]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn shutdown_daemon_stops_daemon() {
+        let daemon = match create_daemon() {
+            Ok(d) => d,
+            Err(_) => return, // Skip if daemon creation fails (e.g. network constraints in CI)
+        };
+        assert!(shutdown_daemon(&daemon).is_ok());
+        // A second shutdown is rejected because the daemon thread is gone
+        assert!(shutdown_daemon(&daemon).is_err());
+    }
 }
@@ -1,14 +1,23 @@
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use chrono::{DateTime, Utc, Weekday};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use std::net::IpAddr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     pub version: u32,
     #[serde(default)]
     pub hostname: String,
+    /// mDNS hostname to advertise instead of the OS hostname.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname_override: Option<String>,
     #[serde(default)]
     pub services: Vec<ServiceConfig>,
+    #[serde(default)]
+    pub aliases: Vec<HostAlias>,
+    /// Remote services to alert on when they appear, disappear or change.
+    #[serde(default)]
+    pub watchlist: Vec<WatchEntry>,
 }
 
 impl Default for AppConfig {
@@ -16,21 +25,438 @@ impl Default for AppConfig {
         Self {
             version: 1,
             hostname: String::new(),
+            hostname_override: None,
             services: Vec::new(),
+            aliases: Vec::new(),
+            watchlist: Vec::new(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// The hostname services are advertised under: the override if set,
+    /// otherwise the OS hostname.
+    pub fn effective_hostname(&self) -> &str {
+        match self.hostname_override.as_deref() {
+            Some(name) if !name.trim().is_empty() => name,
+            _ => &self.hostname,
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostInfo {
+    /// Name services are advertised under.
+    pub effective: String,
+    /// Hostname reported by the OS.
+    pub os: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname_override: Option<String>,
+}
+
+/// A remote service to keep an eye on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchEntry {
+    pub id: String,
+    /// Instance fullname (`Build Cache._http._tcp.local.`) to watch one
+    /// instance, or a service type (`_ipp._tcp`) to watch all of them.
+    pub target: String,
+}
+
+/// What happened to a watched service.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum WatchChange {
+    Appeared,
+    Disappeared,
+    Changed { details: String },
+}
+
+/// Emitted as `watch-event` for each change to a watched service.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchEvent {
+    pub timestamp: DateTime<Utc>,
+    pub watch_id: String,
+    pub fullname: String,
+    #[serde(flatten)]
+    pub change: WatchChange,
+}
+
+/// An extra `.local` name answered with address records, e.g. `api.local`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostAlias {
+    pub id: String,
+    pub name: String,
+    /// Addresses to publish. Empty means this machine's own addresses.
+    #[serde(default)]
+    pub addresses: Vec<IpAddr>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ServiceConfig {
     pub id: String,
     pub name: String,
     #[serde(rename = "type")]
     pub service_type: String,
     pub port: u16,
-    #[serde(default)]
-    pub txt: HashMap<String, String>,
+    #[serde(default, deserialize_with = "deserialize_txt")]
+    pub txt: Vec<TxtEntry>,
+    /// TXT values read from a command or file, refreshed while the service
+    /// is active. They replace static entries with the same key.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub txt_sources: Vec<TxtSource>,
     pub enabled: bool,
+    /// Restricts the interfaces the service is announced on. `None` announces
+    /// on every interface the daemon sees.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interfaces: Option<InterfaceFilter>,
+    /// Advertise on behalf of another host instead of this machine.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyTarget>,
+    /// Hostname for this service only, overriding the global one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    /// DNS-SD subtype (e.g. `_printer`) announced as an extra PTR record.
+    /// mdns-sd keys registrations by instance fullname, so an instance can
+    /// carry only one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtype: Option<String>,
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
+    /// Withdraw the advertisement while the service behind it is down.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheck>,
+    /// Command run while the service is started; the advertisement is
+    /// withdrawn when it exits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process: Option<ManagedProcess>,
+    /// Publish only while a process we did not start is running.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bound_process: Option<ProcessBinding>,
+    /// Publish only inside a recurring local-time window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
+    /// How failed registrations are retried. `None` uses the defaults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+    /// End of a time-limited start, when the service is disabled again.
+    /// Saved so the limit still applies after a restart.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_at: Option<DateTime<Utc>>,
+}
+
+/// A service as sent by the frontend to add or edit one. Fields left out
+/// keep their current value on an edit and their default on an add;
+/// optional settings are removed by sending `null`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ServiceInput {
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub service_type: Option<String>,
+    pub port: Option<u16>,
+    pub txt: Option<Vec<TxtEntry>>,
+    pub txt_sources: Option<Vec<TxtSource>>,
+    pub enabled: Option<bool>,
+    #[serde(deserialize_with = "deserialize_present")]
+    pub interfaces: Option<Option<InterfaceFilter>>,
+    #[serde(deserialize_with = "deserialize_present")]
+    pub proxy: Option<Option<ProxyTarget>>,
+    #[serde(deserialize_with = "deserialize_present")]
+    pub hostname: Option<Option<String>>,
+    #[serde(deserialize_with = "deserialize_present")]
+    pub subtype: Option<Option<String>>,
+    pub conflict_policy: Option<ConflictPolicy>,
+    #[serde(deserialize_with = "deserialize_present")]
+    pub health_check: Option<Option<HealthCheck>>,
+    #[serde(deserialize_with = "deserialize_present")]
+    pub process: Option<Option<ManagedProcess>>,
+    #[serde(deserialize_with = "deserialize_present")]
+    pub bound_process: Option<Option<ProcessBinding>>,
+    #[serde(deserialize_with = "deserialize_present")]
+    pub schedule: Option<Option<Schedule>>,
+    #[serde(deserialize_with = "deserialize_present")]
+    pub retry: Option<Option<RetryPolicy>>,
+}
+
+impl ServiceInput {
+    /// `base` with the fields this input sets replaced.
+    pub fn apply(self, base: &ServiceConfig) -> ServiceConfig {
+        ServiceConfig {
+            id: base.id.clone(),
+            name: self.name.unwrap_or_else(|| base.name.clone()),
+            service_type: self
+                .service_type
+                .unwrap_or_else(|| base.service_type.clone()),
+            port: self.port.unwrap_or(base.port),
+            txt: self.txt.unwrap_or_else(|| base.txt.clone()),
+            txt_sources: self.txt_sources.unwrap_or_else(|| base.txt_sources.clone()),
+            enabled: self.enabled.unwrap_or(base.enabled),
+            interfaces: self.interfaces.unwrap_or_else(|| base.interfaces.clone()),
+            proxy: self.proxy.unwrap_or_else(|| base.proxy.clone()),
+            hostname: self.hostname.unwrap_or_else(|| base.hostname.clone()),
+            subtype: self.subtype.unwrap_or_else(|| base.subtype.clone()),
+            conflict_policy: self.conflict_policy.unwrap_or(base.conflict_policy),
+            health_check: self
+                .health_check
+                .unwrap_or_else(|| base.health_check.clone()),
+            process: self.process.unwrap_or_else(|| base.process.clone()),
+            bound_process: self
+                .bound_process
+                .unwrap_or_else(|| base.bound_process.clone()),
+            schedule: self.schedule.unwrap_or_else(|| base.schedule.clone()),
+            retry: self.retry.unwrap_or_else(|| base.retry.clone()),
+            stop_at: base.stop_at,
+        }
+    }
+}
+
+/// Tell a field sent as `null` apart from one left out: the former
+/// deserializes to `Some(None)`, the latter falls back to `None`.
+fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Periodic probe of the endpoint a service advertises.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthCheck {
+    #[serde(flatten)]
+    pub probe: HealthProbe,
+    #[serde(default = "default_check_interval")]
+    pub interval_secs: u64,
+    /// Consecutive failures before the service is withdrawn.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// Consecutive successes before it is published again.
+    #[serde(default = "default_success_threshold")]
+    pub success_threshold: u32,
+}
+
+fn default_check_interval() -> u64 {
+    10
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+fn default_success_threshold() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "probe", rename_all = "lowercase")]
+pub enum HealthProbe {
+    /// Connect to the advertised port.
+    Tcp,
+    /// GET `path` on the advertised port and expect `expected_status`.
+    Http {
+        path: String,
+        #[serde(default = "default_expected_status")]
+        expected_status: u16,
+    },
+}
+
+fn default_expected_status() -> u16 {
+    200
+}
+
+/// A child process owned by a service.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManagedProcess {
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Working directory; the app's own when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Added to the app's environment.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub restart: RestartPolicy,
+}
+
+/// How to find a process started outside the app.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "by", rename_all = "lowercase")]
+pub enum ProcessBinding {
+    Pid {
+        pid: u32,
+    },
+    /// A file holding the PID, re-read on every check.
+    Pidfile {
+        path: String,
+    },
+    /// Glob matched against the process name or its full command line.
+    Name {
+        pattern: String,
+    },
+}
+
+/// A weekly window, in local time, during which a service is published.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Schedule {
+    /// Days the window opens on; every day when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<Weekday>,
+    /// `HH:MM`. A window that ends before it starts runs past midnight.
+    pub start: String,
+    pub end: String,
+}
+
+/// How a failed registration is retried, waiting twice as long after each
+/// further failure.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Retries before giving up; unlimited when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+    #[serde(default = "default_initial_backoff")]
+    pub initial_backoff_secs: u64,
+    #[serde(default = "default_max_backoff")]
+    pub max_backoff_secs: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: None,
+            initial_backoff_secs: default_initial_backoff(),
+            max_backoff_secs: default_max_backoff(),
+        }
+    }
+}
+
+fn default_initial_backoff() -> u64 {
+    2
+}
+
+fn default_max_backoff() -> u64 {
+    300
+}
+
+/// Whether an exited process is started again, after an increasing delay.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    #[default]
+    Never,
+    /// Restart after a non-zero exit or a signal.
+    OnFailure,
+    Always,
+}
+
+/// What to do when another host already advertises our instance name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    /// Withdraw the advertisement and mark the service as conflicting.
+    #[default]
+    Report,
+    /// Keep the numbered name chosen during probing, e.g. `My Service (2)`.
+    Number,
+    /// Re-advertise with the hostname appended, e.g. `My Service (devbox)`.
+    Hostname,
+}
+
+/// Where a refreshed TXT value comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxtSource {
+    pub key: String,
+    #[serde(flatten)]
+    pub kind: TxtSourceKind,
+    #[serde(default = "default_refresh_interval")]
+    pub interval_secs: u64,
+}
+
+fn default_refresh_interval() -> u64 {
+    60
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "lowercase")]
+pub enum TxtSourceKind {
+    /// Shell command whose trimmed stdout is the value.
+    Command { command: String },
+    /// File whose trimmed contents are the value.
+    File { path: String },
+}
+
+/// One TXT attribute. Entries are published in list order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxtEntry {
+    pub key: String,
+    /// `None` publishes a key-only boolean attribute (`key`), which RFC 6763
+    /// distinguishes from an empty value (`key=`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// `value` holds base64-encoded bytes instead of text.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub binary: bool,
+}
+
+impl TxtEntry {
+    pub fn text(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: Some(value.into()),
+            binary: false,
+        }
+    }
+}
+
+/// Accept both the ordered list format and the `{ "key": "value" }` object
+/// written by earlier versions.
+fn deserialize_txt<'de, D>(deserializer: D) -> Result<Vec<TxtEntry>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum TxtFormat {
+        List(Vec<TxtEntry>),
+        Map(BTreeMap<String, String>),
+    }
+
+    Ok(match TxtFormat::deserialize(deserializer)? {
+        TxtFormat::List(entries) => entries,
+        TxtFormat::Map(map) => map.into_iter().map(|(k, v)| TxtEntry::text(k, v)).collect(),
+    })
+}
+
+/// A host that cannot run its own responder, advertised with fixed addresses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxyTarget {
+    /// Target hostname, e.g. `printer` or `printer.local`.
+    pub host: String,
+    pub addresses: Vec<IpAddr>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InterfaceFilterMode {
+    /// Announce only on interfaces matching one of the patterns.
+    #[default]
+    Allow,
+    /// Announce on every interface except those matching one of the patterns.
+    Deny,
+}
+
+/// Interface selection for a single service.
+///
+/// Each pattern is either an interface name glob (`eth*`, `en?`) or a subnet
+/// in CIDR notation (`192.168.1.0/24`, `fd00::/8`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterfaceFilter {
+    #[serde(default)]
+    pub mode: InterfaceFilterMode,
+    pub patterns: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -39,6 +465,30 @@ pub enum ServiceStatus {
     Running,
     Stopped,
     Error,
+    Conflict,
+    /// Enabled, but withdrawn because its health check is failing.
+    Unhealthy,
+    /// Enabled, but withdrawn until its bound process is running or its
+    /// health check first passes.
+    Waiting,
+    /// Enabled, but outside its schedule.
+    Scheduled,
+    /// Registration failed; another attempt is pending.
+    Retrying,
+}
+
+impl ServiceStatus {
+    /// Whether the service is switched on, even if currently withdrawn.
+    pub fn is_active(self) -> bool {
+        matches!(
+            self,
+            ServiceStatus::Running
+                | ServiceStatus::Unhealthy
+                | ServiceStatus::Waiting
+                | ServiceStatus::Scheduled
+                | ServiceStatus::Retrying
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,9 +498,69 @@ pub struct ServiceView {
     #[serde(rename = "type")]
     pub service_type: String,
     pub port: u16,
-    pub txt: HashMap<String, String>,
+    pub txt: Vec<TxtEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub txt_sources: Vec<TxtSource>,
     pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interfaces: Option<InterfaceFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyTarget>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtype: Option<String>,
+    pub conflict_policy: ConflictPolicy,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheck>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process: Option<ManagedProcess>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bound_process: Option<ProcessBinding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+    /// When a time-limited start ends.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stops_at: Option<DateTime<Utc>>,
+    /// When the schedule next opens or closes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_transition: Option<DateTime<Utc>>,
+    /// PID of the managed process while it runs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    /// `name` with its templates expanded, while published and different.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendered_name: Option<String>,
+    /// `txt` with its templates expanded, while published and different.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendered_txt: Option<Vec<TxtEntry>>,
+    /// Name actually on the wire when it differs from `name` after a conflict.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published_name: Option<String>,
+    /// When the current advertisement was last announced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<DateTime<Utc>>,
+    /// Failed registrations so far, while retrying.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_attempt: Option<u32>,
+    /// When the next registration attempt is due, while retrying.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_retry: Option<DateTime<Utc>>,
     pub status: ServiceStatus,
+    /// Outcome of resolving our own advertisement after it was published.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification: Option<Verification>,
+}
+
+/// Whether browsing for a published service returned what we advertised.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "lowercase")]
+pub enum Verification {
+    Pending,
+    Verified,
+    Unverified { reason: String },
 }
 
 impl ServiceView {
@@ -61,7 +571,130 @@ impl ServiceView {
             service_type: config.service_type.clone(),
             port: config.port,
             txt: config.txt.clone(),
+            txt_sources: config.txt_sources.clone(),
             enabled: config.enabled,
+            interfaces: config.interfaces.clone(),
+            proxy: config.proxy.clone(),
+            hostname: config.hostname.clone(),
+            subtype: config.subtype.clone(),
+            conflict_policy: config.conflict_policy,
+            health_check: config.health_check.clone(),
+            process: config.process.clone(),
+            bound_process: config.bound_process.clone(),
+            schedule: config.schedule.clone(),
+            retry: config.retry.clone(),
+            stops_at: None,
+            next_transition: None,
+            pid: None,
+            rendered_name: None,
+            rendered_txt: None,
+            published_name: None,
+            published_at: None,
+            retry_attempt: None,
+            next_retry: None,
+            status,
+            verification: None,
+        }
+    }
+}
+
+/// What a registration actually put on the wire, kept apart from the
+/// editable config so that withdrawing targets the published records.
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishedRecord {
+    pub service_id: String,
+    /// Fullname on the wire, which follows a conflict rename.
+    pub fullname: String,
+    /// Fullname the daemon keys the registration by. mdns-sd keeps it when
+    /// it renames the instance, so unregistering has to use it.
+    pub registered_fullname: String,
+    /// Instance part of `fullname`, which differs from the configured name
+    /// after a template expansion or a conflict rename.
+    pub instance_name: String,
+    /// Target of the SRV record.
+    pub host: String,
+    pub port: u16,
+    pub txt: Vec<TxtEntry>,
+    /// Announced A/AAAA addresses: the proxied ones, or this machine's on
+    /// the interfaces published on.
+    pub addresses: Vec<String>,
+    /// Interfaces the service is announced on; empty when published on all.
+    pub interfaces: Vec<String>,
+    pub published_at: DateTime<Utc>,
+    /// The service as registered, with its templates expanded.
+    #[serde(skip)]
+    pub config: ServiceConfig,
+}
+
+impl PublishedRecord {
+    /// The registered config under the instance name on the wire.
+    pub fn announced(&self) -> ServiceConfig {
+        let mut announced = self.config.clone();
+        announced.name.clone_from(&self.instance_name);
+        announced
+    }
+}
+
+/// A service instance resolved while browsing the network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveredService {
+    pub fullname: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub service_type: String,
+    pub host: String,
+    pub port: u16,
+    pub addresses: Vec<IpAddr>,
+    pub txt: Vec<TxtEntry>,
+    /// Published by this app.
+    pub own: bool,
+}
+
+/// Streamed to the frontend as `browse-event` while a browse is active.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BrowseEvent {
+    Resolved {
+        service: DiscoveredService,
+    },
+    Removed {
+        #[serde(rename = "type")]
+        service_type: String,
+        fullname: String,
+    },
+    Stopped {
+        #[serde(rename = "type")]
+        service_type: String,
+    },
+}
+
+/// A service type seen on the network, with the number of instances
+/// currently announced under it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceTypeSummary {
+    #[serde(rename = "type")]
+    pub service_type: String,
+    pub instances: usize,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AliasView {
+    pub id: String,
+    pub name: String,
+    pub addresses: Vec<IpAddr>,
+    pub enabled: bool,
+    pub status: ServiceStatus,
+}
+
+impl AliasView {
+    pub fn from_config(alias: &HostAlias, status: ServiceStatus) -> Self {
+        Self {
+            id: alias.id.clone(),
+            name: alias.name.clone(),
+            addresses: alias.addresses.clone(),
+            enabled: alias.enabled,
             status,
         }
     }
@@ -84,6 +717,25 @@ pub struct LogEntry {
     pub service_id: Option<String>,
 }
 
+/// A network-level event reported by an mDNS daemon.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DaemonEventKind {
+    Error { message: String },
+    IpAdded { address: IpAddr },
+    IpRemoved { address: IpAddr },
+    NameChanged { original: String, new_name: String },
+    Announced { fullname: String, interface: String },
+}
+
+/// Payload of the `daemon-event` Tauri event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonEventEntry {
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: DaemonEventKind,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkInterface {
     pub name: String,
@@ -100,8 +752,9 @@ mod tests {
             name: "My Service".into(),
             service_type: "_http._tcp".into(),
             port: 8080,
-            txt: HashMap::from([("path".into(), "/".into())]),
+            txt: vec![TxtEntry::text("path", "/")],
             enabled: true,
+            ..Default::default()
         }
     }
 
@@ -125,6 +778,140 @@ mod tests {
         let config: ServiceConfig = serde_json::from_str(json).unwrap();
         assert_eq!(config.service_type, "_http._tcp");
         assert!(config.txt.is_empty());
+        assert!(config.interfaces.is_none());
+    }
+
+    #[test]
+    fn service_input_keeps_fields_left_out() {
+        let base: ServiceConfig = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "name": "Test",
+            "type": "_http._tcp",
+            "port": 80,
+            "enabled": true,
+            "hostname": "nas",
+            "conflict_policy": "hostname",
+            "health_check": { "probe": "tcp" },
+            "process": { "command": "python3" },
+        }))
+        .unwrap();
+        let input: ServiceInput = serde_json::from_value(serde_json::json!({
+            "name": "Renamed",
+            "port": 8080,
+        }))
+        .unwrap();
+
+        let updated = input.apply(&base);
+        assert_eq!(updated.name, "Renamed");
+        assert_eq!(updated.port, 8080);
+        assert_eq!(updated.service_type, "_http._tcp");
+        assert_eq!(updated.hostname.as_deref(), Some("nas"));
+        assert_eq!(updated.conflict_policy, ConflictPolicy::Hostname);
+        assert_eq!(updated.health_check, base.health_check);
+        assert_eq!(updated.process, base.process);
+    }
+
+    #[test]
+    fn service_input_null_clears_setting() {
+        let mut base = sample_service_config();
+        base.hostname = Some("nas".into());
+        let input: ServiceInput =
+            serde_json::from_value(serde_json::json!({ "hostname": null })).unwrap();
+
+        let updated = input.apply(&base);
+        assert!(updated.hostname.is_none());
+        assert_eq!(updated.name, base.name);
+    }
+
+    #[test]
+    fn interface_filter_mode_defaults_to_allow() {
+        let json = r#"{ "patterns": ["eth*", "192.168.1.0/24"] }"#;
+        let filter: InterfaceFilter = serde_json::from_str(json).unwrap();
+        assert_eq!(filter.mode, InterfaceFilterMode::Allow);
+        assert_eq!(filter.patterns.len(), 2);
+    }
+
+    #[test]
+    fn proxy_target_roundtrip() {
+        let json = r#"{
+            "id": "1",
+            "name": "Printer",
+            "type": "_ipp._tcp",
+            "port": 631,
+            "enabled": true,
+            "proxy": { "host": "printer", "addresses": ["192.168.1.50", "fe80::50"] }
+        }"#;
+        let config: ServiceConfig = serde_json::from_str(json).unwrap();
+        let proxy = config.proxy.as_ref().unwrap();
+        assert_eq!(proxy.host, "printer");
+        assert_eq!(proxy.addresses.len(), 2);
+
+        let view = ServiceView::from_config(&config, ServiceStatus::Stopped);
+        let json = serde_json::to_value(&view).unwrap();
+        assert_eq!(json["proxy"]["addresses"][0], "192.168.1.50");
+    }
+
+    #[test]
+    fn subtype_roundtrips_through_app_config() {
+        let mut service = sample_service_config();
+        service.subtype = Some("_printer".into());
+        let config = AppConfig {
+            services: vec![service],
+            ..AppConfig::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let loaded: AppConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.services[0].subtype.as_deref(), Some("_printer"));
+    }
+
+    #[test]
+    fn service_config_omits_missing_interface_filter() {
+        let config = sample_service_config();
+        let json = serde_json::to_value(&config).unwrap();
+        assert!(json.get("interfaces").is_none());
+    }
+
+    #[test]
+    fn txt_legacy_object_format_deserializes() {
+        let json = r#"{
+            "id": "1",
+            "name": "Test",
+            "type": "_http._tcp",
+            "port": 80,
+            "txt": { "path": "/", "version": "2" },
+            "enabled": false
+        }"#;
+        let config: ServiceConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.txt,
+            vec![TxtEntry::text("path", "/"), TxtEntry::text("version", "2")]
+        );
+    }
+
+    #[test]
+    fn txt_list_preserves_order_and_flags() {
+        let mut config = sample_service_config();
+        config.txt = vec![
+            TxtEntry::text("z", "last-key-first"),
+            TxtEntry {
+                key: "secure".into(),
+                value: None,
+                binary: false,
+            },
+            TxtEntry {
+                key: "blob".into(),
+                value: Some("AAEC".into()),
+                binary: true,
+            },
+        ];
+        let json = serde_json::to_string(&config).unwrap();
+        let loaded: ServiceConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.txt, config.txt);
+
+        let value = serde_json::to_value(&config).unwrap();
+        assert!(value["txt"][0].get("binary").is_none());
+        assert!(value["txt"][1].get("value").is_none());
+        assert_eq!(value["txt"][2]["binary"], true);
     }
 
     #[test]
@@ -141,6 +928,191 @@ mod tests {
             serde_json::to_string(&ServiceStatus::Error).unwrap(),
             "\"error\""
         );
+        assert_eq!(
+            serde_json::to_string(&ServiceStatus::Conflict).unwrap(),
+            "\"conflict\""
+        );
+    }
+
+    #[test]
+    fn conflict_policy_defaults_to_report() {
+        let json = r#"{
+            "id": "1",
+            "name": "Test",
+            "type": "_http._tcp",
+            "port": 80,
+            "enabled": true
+        }"#;
+        let config: ServiceConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.conflict_policy, ConflictPolicy::Report);
+    }
+
+    #[test]
+    fn verification_serializes_with_state_tag() {
+        let json = serde_json::to_value(Verification::Verified).unwrap();
+        assert_eq!(json, serde_json::json!({ "state": "verified" }));
+
+        let json = serde_json::to_value(Verification::Unverified {
+            reason: "port 80 instead of 8080".into(),
+        })
+        .unwrap();
+        assert_eq!(json["state"], "unverified");
+        assert_eq!(json["reason"], "port 80 instead of 8080");
+    }
+
+    #[test]
+    fn browse_event_serializes_with_kind_tag() {
+        let event = BrowseEvent::Removed {
+            service_type: "_http._tcp.local.".into(),
+            fullname: "Web._http._tcp.local.".into(),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["kind"], "removed");
+        assert_eq!(json["type"], "_http._tcp.local.");
+        assert_eq!(json["fullname"], "Web._http._tcp.local.");
+    }
+
+    #[test]
+    fn watchlist_defaults_to_empty() {
+        let config: AppConfig = serde_json::from_str(r#"{ "version": 1 }"#).unwrap();
+        assert!(config.watchlist.is_empty());
+    }
+
+    #[test]
+    fn watch_event_flattens_change() {
+        let event = WatchEvent {
+            timestamp: Utc::now(),
+            watch_id: "w1".into(),
+            fullname: "Cache._http._tcp.local.".into(),
+            change: WatchChange::Changed {
+                details: "port 80 -> 8080".into(),
+            },
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["change"], "changed");
+        assert_eq!(json["details"], "port 80 -> 8080");
+        assert_eq!(json["watch_id"], "w1");
+    }
+
+    #[test]
+    fn health_check_defaults_and_probe_tag() {
+        let check: HealthCheck =
+            serde_json::from_str(r#"{ "probe": "http", "path": "/health" }"#).unwrap();
+        assert_eq!(
+            check.probe,
+            HealthProbe::Http {
+                path: "/health".into(),
+                expected_status: 200
+            }
+        );
+        assert_eq!(check.interval_secs, 10);
+        assert_eq!(check.failure_threshold, 3);
+        assert_eq!(check.success_threshold, 1);
+
+        let json = serde_json::to_value(HealthCheck {
+            probe: HealthProbe::Tcp,
+            interval_secs: 5,
+            failure_threshold: 2,
+            success_threshold: 2,
+        })
+        .unwrap();
+        assert_eq!(json["probe"], "tcp");
+        assert_eq!(json["interval_secs"], 5);
+    }
+
+    #[test]
+    fn unhealthy_counts_as_active() {
+        assert!(ServiceStatus::Running.is_active());
+        assert!(ServiceStatus::Unhealthy.is_active());
+        assert!(ServiceStatus::Waiting.is_active());
+        assert!(ServiceStatus::Scheduled.is_active());
+        assert!(ServiceStatus::Retrying.is_active());
+        assert!(!ServiceStatus::Stopped.is_active());
+        assert!(!ServiceStatus::Error.is_active());
+        assert_eq!(
+            serde_json::to_string(&ServiceStatus::Unhealthy).unwrap(),
+            "\"unhealthy\""
+        );
+    }
+
+    #[test]
+    fn retry_policy_defaults() {
+        let policy: RetryPolicy = serde_json::from_str(r#"{ "max_attempts": 5 }"#).unwrap();
+        assert_eq!(
+            policy,
+            RetryPolicy {
+                max_attempts: Some(5),
+                ..RetryPolicy::default()
+            }
+        );
+        assert_eq!(policy.initial_backoff_secs, 2);
+        assert_eq!(policy.max_backoff_secs, 300);
+
+        let json = serde_json::to_value(RetryPolicy::default()).unwrap();
+        assert!(json.get("max_attempts").is_none());
+    }
+
+    #[test]
+    fn managed_process_defaults() {
+        let process: ManagedProcess =
+            serde_json::from_str(r#"{ "command": "npm", "args": ["run", "dev"] }"#).unwrap();
+        assert_eq!(process.args, vec!["run", "dev"]);
+        assert_eq!(process.cwd, None);
+        assert!(process.env.is_empty());
+        assert_eq!(process.restart, RestartPolicy::Never);
+        assert_eq!(
+            serde_json::to_string(&RestartPolicy::OnFailure).unwrap(),
+            "\"on-failure\""
+        );
+    }
+
+    #[test]
+    fn process_binding_tagged_by_kind() {
+        let binding: ProcessBinding =
+            serde_json::from_str(r#"{ "by": "pidfile", "path": "/run/app.pid" }"#).unwrap();
+        assert_eq!(
+            binding,
+            ProcessBinding::Pidfile {
+                path: "/run/app.pid".into()
+            }
+        );
+        let json = serde_json::to_value(ProcessBinding::Name {
+            pattern: "*vite*".into(),
+        })
+        .unwrap();
+        assert_eq!(json["by"], "name");
+        assert_eq!(json["pattern"], "*vite*");
+    }
+
+    #[test]
+    fn schedule_days_accept_short_names() {
+        let schedule: Schedule = serde_json::from_str(
+            r#"{ "days": ["Mon", "tue", "Friday"], "start": "09:00", "end": "19:00" }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            schedule.days,
+            vec![Weekday::Mon, Weekday::Tue, Weekday::Fri]
+        );
+
+        let every_day: Schedule =
+            serde_json::from_str(r#"{ "start": "22:00", "end": "02:00" }"#).unwrap();
+        assert!(every_day.days.is_empty());
+    }
+
+    #[test]
+    fn txt_source_defaults_interval() {
+        let source: TxtSource = serde_json::from_str(
+            r#"{ "key": "commit", "source": "command", "command": "git rev-parse --short HEAD" }"#,
+        )
+        .unwrap();
+        assert_eq!(source.interval_secs, 60);
+        assert_eq!(
+            source.kind,
+            TxtSourceKind::Command {
+                command: "git rev-parse --short HEAD".into()
+            }
+        );
     }
 
     #[test]
@@ -199,11 +1171,85 @@ mod tests {
         assert_eq!(json["service_id"], "svc-1");
     }
 
+    #[test]
+    fn daemon_event_entry_serializes_flat_with_kind() {
+        let entry = DaemonEventEntry {
+            timestamp: Utc::now(),
+            event: DaemonEventKind::IpRemoved {
+                address: "192.168.1.20".parse().unwrap(),
+            },
+        };
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["kind"], "ip_removed");
+        assert_eq!(json["address"], "192.168.1.20");
+        assert!(json.get("timestamp").is_some());
+    }
+
     #[test]
     fn app_config_default() {
         let config = AppConfig::default();
         assert_eq!(config.version, 1);
         assert!(config.hostname.is_empty());
         assert!(config.services.is_empty());
+        assert!(config.aliases.is_empty());
+    }
+
+    #[test]
+    fn effective_hostname_prefers_override() {
+        let mut config = AppConfig {
+            hostname: "os-name".into(),
+            ..AppConfig::default()
+        };
+        assert_eq!(config.effective_hostname(), "os-name");
+        config.hostname_override = Some("devbox".into());
+        assert_eq!(config.effective_hostname(), "devbox");
+        config.hostname_override = Some("  ".into());
+        assert_eq!(config.effective_hostname(), "os-name");
+    }
+
+    #[test]
+    fn app_config_without_aliases_deserializes() {
+        let json = r#"{ "version": 1, "services": [] }"#;
+        let config: AppConfig = serde_json::from_str(json).unwrap();
+        assert!(config.aliases.is_empty());
+    }
+
+    #[test]
+    fn alias_view_from_config() {
+        let alias = HostAlias {
+            id: "alias-1".into(),
+            name: "api".into(),
+            addresses: vec!["192.168.1.10".parse().unwrap()],
+            enabled: true,
+        };
+        let view = AliasView::from_config(&alias, ServiceStatus::Running);
+        let json = serde_json::to_value(&view).unwrap();
+        assert_eq!(json["name"], "api");
+        assert_eq!(json["addresses"][0], "192.168.1.10");
+        assert_eq!(json["status"], "running");
+    }
+
+    #[test]
+    fn published_record_announces_under_instance_name() {
+        let record = PublishedRecord {
+            service_id: "test-id".into(),
+            fullname: "My Service (2)._http._tcp.local.".into(),
+            registered_fullname: "My Service._http._tcp.local.".into(),
+            instance_name: "My Service (2)".into(),
+            host: "myhost.local.".into(),
+            port: 8080,
+            txt: Vec::new(),
+            addresses: vec!["192.168.1.10".into()],
+            interfaces: Vec::new(),
+            published_at: Utc::now(),
+            config: sample_service_config(),
+        };
+        assert_eq!(record.announced().name, "My Service (2)");
+        assert_eq!(record.announced().port, record.config.port);
+
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["fullname"], "My Service (2)._http._tcp.local.");
+        assert_eq!(json["registered_fullname"], "My Service._http._tcp.local.");
+        assert!(json.get("config").is_none());
     }
 }
@@ -0,0 +1,312 @@
+use crate::commands;
+use crate::error::AppError;
+use crate::logging;
+use crate::mdns;
+use crate::models::{
+    ConflictPolicy, DaemonEventEntry, DaemonEventKind, LogLevel, ServiceConfig, ServiceStatus,
+};
+use crate::network;
+use crate::reconcile::{self, Trigger};
+use crate::state::AppState;
+use crate::template;
+use crate::verify;
+use chrono::Utc;
+use mdns_sd::{DaemonEvent, DnsNameChange, ServiceDaemon};
+use std::thread;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Handle `daemon`'s monitor events on a background thread. The thread
+/// exits once the daemon shuts down and the channel closes.
+pub fn watch_daemon(app: &AppHandle, daemon: &ServiceDaemon) {
+    let receiver = match daemon.monitor() {
+        Ok(receiver) => receiver,
+        Err(e) => {
+            let state = app.state::<AppState>();
+            logging::append_log(
+                app,
+                &state,
+                LogLevel::Warn,
+                format!("Failed to monitor mDNS daemon: {}", e),
+                None,
+            );
+            return;
+        }
+    };
+
+    let app = app.clone();
+    thread::spawn(move || {
+        while let Ok(event) = receiver.recv() {
+            handle_event(&app, event);
+        }
+    });
+}
+
+fn handle_event(app: &AppHandle, event: DaemonEvent) {
+    let state = app.state::<AppState>();
+
+    let kind = match event {
+        DaemonEvent::Error(e) => {
+            let message = e.to_string();
+            logging::append_log(
+                app,
+                &state,
+                LogLevel::Error,
+                format!("mDNS daemon error: {}", message),
+                None,
+            );
+            mark_failed_services(app, &state, &message);
+            DaemonEventKind::Error { message }
+        }
+        DaemonEvent::IpAdd(address) => {
+            logging::append_log(
+                app,
+                &state,
+                LogLevel::Info,
+                format!("Network address added: {}", address),
+                None,
+            );
+            refresh_filtered_services(app, &state);
+            reconcile::reconcile(app, &state, Trigger::InterfacesChanged);
+            DaemonEventKind::IpAdded { address }
+        }
+        DaemonEvent::IpDel(address) => {
+            logging::append_log(
+                app,
+                &state,
+                LogLevel::Warn,
+                format!("Network address removed: {}", address),
+                None,
+            );
+            refresh_filtered_services(app, &state);
+            reconcile::reconcile(app, &state, Trigger::InterfacesChanged);
+            DaemonEventKind::IpRemoved { address }
+        }
+        DaemonEvent::NameChange(change) => {
+            handle_name_change(app, &change);
+            DaemonEventKind::NameChanged {
+                original: change.original,
+                new_name: change.new_name,
+            }
+        }
+        DaemonEvent::Announce(fullname, interface) => DaemonEventKind::Announced {
+            fullname,
+            interface,
+        },
+        _ => return,
+    };
+
+    let entry = DaemonEventEntry {
+        timestamp: Utc::now(),
+        event: kind,
+    };
+    let _ = app.emit("daemon-event", &entry);
+}
+
+/// Withdraw running services named in a daemon error and retry them like
+/// any other failed registration.
+fn mark_failed_services(app: &AppHandle, state: &AppState, message: &str) {
+    let failed: Vec<ServiceConfig> = {
+        let config = state.config.lock().unwrap();
+        let statuses = state.statuses.lock().unwrap();
+        config
+            .services
+            .iter()
+            .filter(|s| statuses.get(&s.id).copied() == Some(ServiceStatus::Running))
+            .filter(|s| {
+                let published = state.published.lock().unwrap();
+                published
+                    .get(&s.id)
+                    .is_some_and(|r| message.contains(&r.fullname))
+            })
+            .cloned()
+            .collect()
+    };
+    if failed.is_empty() {
+        return;
+    }
+
+    let error = AppError::Mdns(message.to_string());
+    for svc in &failed {
+        let _ = commands::unregister_on_daemon(state, svc, false);
+        commands::registration_failed(app, state, svc, "Failed to publish", &error);
+    }
+    if let Ok(views) = commands::build_views(state) {
+        let _ = app.emit("services-changed", &views);
+    }
+}
+
+/// Interfaces came or went: re-select interfaces for running services with
+/// an interface filter and re-publish those whose selection changed.
+fn refresh_filtered_services(app: &AppHandle, state: &AppState) {
+    let (services, hostname) = {
+        let config = state.config.lock().unwrap();
+        let statuses = state.statuses.lock().unwrap();
+        let services: Vec<ServiceConfig> = config
+            .services
+            .iter()
+            .filter(|s| s.interfaces.is_some())
+            .filter(|s| statuses.get(&s.id).copied() == Some(ServiceStatus::Running))
+            .cloned()
+            .collect();
+        (services, config.effective_hostname().to_string())
+    };
+    if services.is_empty() {
+        return;
+    }
+
+    let available = network::get_interfaces();
+    for svc in &services {
+        let Some(filter) = &svc.interfaces else {
+            continue;
+        };
+        let current = {
+            let published = state.published.lock().unwrap();
+            published.get(&svc.id).map(|r| r.interfaces.clone())
+        };
+        match network::select_interfaces(filter, &available) {
+            Ok(names) if Some(&names) == current.as_ref() => {}
+            Ok(names) => {
+                logging::append_log(
+                    app,
+                    state,
+                    LogLevel::Info,
+                    format!(
+                        "Interfaces for service '{}' changed to [{}]",
+                        svc.name,
+                        names.join(", ")
+                    ),
+                    Some(svc.id.clone()),
+                );
+                reconcile::republish(app, state, svc, &hostname);
+            }
+            Err(e) => {
+                let _ = commands::unregister_on_daemon(state, svc, false);
+                commands::registration_failed(app, state, svc, "Withdrew", &e);
+            }
+        }
+    }
+
+    if let Ok(views) = commands::build_views(state) {
+        let _ = app.emit("services-changed", &views);
+    }
+}
+
+/// Probing found our name in use and mdns-sd renamed the instance. Apply the
+/// service's conflict policy to the renamed advertisement.
+fn handle_name_change(app: &AppHandle, change: &DnsNameChange) {
+    let state = app.state::<AppState>();
+
+    // mdns-sd reports the name the registration was made under, which is
+    // also the one it has to be withdrawn by
+    let matches = |svc: &ServiceConfig| {
+        let published = state.published.lock().unwrap();
+        match published.get(&svc.id) {
+            Some(record) => record
+                .registered_fullname
+                .eq_ignore_ascii_case(&change.original),
+            None => mdns::service_fullname(svc).eq_ignore_ascii_case(&change.original),
+        }
+    };
+
+    let (svc, hostname, running) = {
+        let config = state.config.lock().unwrap();
+        let Some(svc) = config.services.iter().find(|s| matches(s)).cloned() else {
+            drop(config);
+            logging::append_log(
+                app,
+                &state,
+                LogLevel::Warn,
+                format!(
+                    "Name conflict: '{}' is in use on the network, renamed to '{}'",
+                    change.original, change.new_name
+                ),
+                None,
+            );
+            return;
+        };
+        let statuses = state.statuses.lock().unwrap();
+        let running = statuses.get(&svc.id).copied() == Some(ServiceStatus::Running);
+        (svc, config.effective_hostname().to_string(), running)
+    };
+
+    // Already withdrawn or handled through another interface's event
+    let Some(new_instance) = mdns::instance_name_of(&change.new_name, &svc.service_type) else {
+        return;
+    };
+    let current = commands::published_config(&state, &svc).name;
+    if !running || current == new_instance {
+        return;
+    }
+
+    // Record the name mdns-sd fell back to for display and browse matching
+    commands::record_instance_name(&state, &svc.id, &new_instance);
+
+    let host_label = match (&svc.proxy, &svc.hostname) {
+        (Some(proxy), _) => proxy.host.clone(),
+        (None, Some(own)) => own.clone(),
+        (None, None) => hostname.clone(),
+    };
+    let host_label = host_label.trim_end_matches('.').trim_end_matches(".local");
+    let base_name = template::render(&svc.name, &hostname).unwrap_or_else(|_| svc.name.clone());
+    let host_renamed = format!("{} ({})", base_name, host_label);
+
+    match svc.conflict_policy {
+        ConflictPolicy::Hostname if current != host_renamed => {
+            let _ = commands::unregister_on_daemon(&state, &svc, false);
+            let mut renamed = svc.clone();
+            renamed.name.clone_from(&host_renamed);
+            match commands::register_on_daemon(&state, &renamed, &hostname) {
+                Ok(()) => {
+                    verify::spawn_verification(app, &svc, &hostname);
+                    logging::append_log(
+                        app,
+                        &state,
+                        LogLevel::Warn,
+                        format!(
+                            "Name '{}' is already in use on the network, published as '{}'",
+                            current, host_renamed
+                        ),
+                        Some(svc.id.clone()),
+                    );
+                }
+                Err(e) => {
+                    commands::registration_failed(app, &state, &svc, "Failed to re-publish", &e);
+                }
+            }
+        }
+        ConflictPolicy::Number | ConflictPolicy::Hostname => {
+            logging::append_log(
+                app,
+                &state,
+                LogLevel::Warn,
+                format!(
+                    "Name '{}' is already in use on the network, published as '{}'",
+                    current, new_instance
+                ),
+                Some(svc.id.clone()),
+            );
+            verify::spawn_verification(app, &svc, &hostname);
+        }
+        ConflictPolicy::Report => {
+            let _ = commands::unregister_on_daemon(&state, &svc, false);
+            {
+                let mut statuses = state.statuses.lock().unwrap();
+                statuses.insert(svc.id.clone(), ServiceStatus::Conflict);
+            }
+            logging::append_log(
+                app,
+                &state,
+                LogLevel::Error,
+                format!(
+                    "Name '{}' is already in use on the network, service withdrawn",
+                    current
+                ),
+                Some(svc.id.clone()),
+            );
+        }
+    }
+
+    if let Ok(views) = commands::build_views(&state) {
+        let _ = app.emit("services-changed", &views);
+    }
+}
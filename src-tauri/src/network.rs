@@ -1,5 +1,7 @@
-use crate::models::NetworkInterface;
+use crate::error::AppError;
+use crate::models::{InterfaceFilter, InterfaceFilterMode, NetworkInterface};
 use std::collections::BTreeMap;
+use std::net::IpAddr;
 
 pub fn get_interfaces() -> Vec<NetworkInterface> {
     let addrs = match if_addrs::get_if_addrs() {
@@ -24,10 +26,149 @@ pub fn get_interfaces() -> Vec<NetworkInterface> {
         .collect()
 }
 
+/// Check that every pattern in `filter` is a usable glob or subnet.
+pub fn validate_filter(filter: &InterfaceFilter) -> Result<(), AppError> {
+    if filter.patterns.is_empty() {
+        return Err(AppError::Config(
+            "Interface filter needs at least one pattern".into(),
+        ));
+    }
+    for pattern in &filter.patterns {
+        if pattern.contains('/') {
+            parse_subnet(pattern)?;
+        } else if pattern.trim().is_empty() {
+            return Err(AppError::Config("Interface pattern is empty".into()));
+        }
+    }
+    Ok(())
+}
+
+/// Return the names of the interfaces a service using `filter` should be
+/// announced on.
+pub fn select_interfaces(
+    filter: &InterfaceFilter,
+    interfaces: &[NetworkInterface],
+) -> Result<Vec<String>, AppError> {
+    validate_filter(filter)?;
+
+    let mut selected = Vec::new();
+    for iface in interfaces {
+        let mut matched = false;
+        for pattern in &filter.patterns {
+            if pattern_matches(pattern, iface)? {
+                matched = true;
+                break;
+            }
+        }
+        let keep = match filter.mode {
+            InterfaceFilterMode::Allow => matched,
+            InterfaceFilterMode::Deny => !matched,
+        };
+        if keep {
+            selected.push(iface.name.clone());
+        }
+    }
+
+    if selected.is_empty() {
+        return Err(AppError::Config(format!(
+            "No network interface matches filter [{}]",
+            filter.patterns.join(", ")
+        )));
+    }
+    Ok(selected)
+}
+
+fn pattern_matches(pattern: &str, iface: &NetworkInterface) -> Result<bool, AppError> {
+    if !pattern.contains('/') {
+        return Ok(glob_match(pattern, &iface.name));
+    }
+    let (network, prefix) = parse_subnet(pattern)?;
+    Ok(iface
+        .addresses
+        .iter()
+        .filter_map(|a| a.parse::<IpAddr>().ok())
+        .any(|addr| in_subnet(addr, network, prefix)))
+}
+
+/// Match `text` against a shell-style glob supporting `*` and `?`.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+fn parse_subnet(cidr: &str) -> Result<(IpAddr, u8), AppError> {
+    let invalid = || AppError::Config(format!("Invalid subnet '{}'", cidr));
+    let (addr, prefix) = cidr.split_once('/').ok_or_else(invalid)?;
+    let addr: IpAddr = addr.trim().parse().map_err(|_| invalid())?;
+    let prefix: u8 = prefix.trim().parse().map_err(|_| invalid())?;
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    if prefix > max {
+        return Err(invalid());
+    }
+    Ok((addr, prefix))
+}
+
+fn in_subnet(addr: IpAddr, network: IpAddr, prefix: u8) -> bool {
+    match (addr, network) {
+        (IpAddr::V4(a), IpAddr::V4(n)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            u32::from(a) & mask == u32::from(n) & mask
+        }
+        (IpAddr::V6(a), IpAddr::V6(n)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            u128::from(a) & mask == u128::from(n) & mask
+        }
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn sample_interfaces() -> Vec<NetworkInterface> {
+        vec![
+            NetworkInterface {
+                name: "docker0".into(),
+                addresses: vec!["172.17.0.1".into()],
+            },
+            NetworkInterface {
+                name: "eth0".into(),
+                addresses: vec!["192.168.1.20".into(), "fe80::1".into()],
+            },
+            NetworkInterface {
+                name: "veth1a2b".into(),
+                addresses: vec!["fe80::2".into()],
+            },
+        ]
+    }
+
+    fn filter(mode: InterfaceFilterMode, patterns: &[&str]) -> InterfaceFilter {
+        InterfaceFilter {
+            mode,
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
     #[test]
     fn get_interfaces_returns_without_error() {
         // get_interfaces() should not panic and should return a valid Vec.
@@ -72,4 +213,49 @@ mod tests {
             "Interface names should be unique (grouped by BTreeMap)"
         );
     }
+
+    #[test]
+    fn glob_match_wildcards() {
+        assert!(glob_match("eth*", "eth0"));
+        assert!(glob_match("en?", "en0"));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("v*h*", "veth1a2b"));
+        assert!(!glob_match("en?", "en10"));
+        assert!(!glob_match("eth*", "veth0"));
+    }
+
+    #[test]
+    fn select_interfaces_allow_by_name_and_subnet() {
+        let f = filter(InterfaceFilterMode::Allow, &["eth*", "172.17.0.0/16"]);
+        let selected = select_interfaces(&f, &sample_interfaces()).unwrap();
+        assert_eq!(selected, vec!["docker0", "eth0"]);
+    }
+
+    #[test]
+    fn select_interfaces_deny_excludes_matches() {
+        let f = filter(InterfaceFilterMode::Deny, &["docker*", "veth*"]);
+        let selected = select_interfaces(&f, &sample_interfaces()).unwrap();
+        assert_eq!(selected, vec!["eth0"]);
+    }
+
+    #[test]
+    fn select_interfaces_ipv6_subnet() {
+        let f = filter(InterfaceFilterMode::Allow, &["fe80::/64"]);
+        let selected = select_interfaces(&f, &sample_interfaces()).unwrap();
+        assert_eq!(selected, vec!["eth0", "veth1a2b"]);
+    }
+
+    #[test]
+    fn select_interfaces_no_match_is_error() {
+        let f = filter(InterfaceFilterMode::Allow, &["wlan*"]);
+        assert!(select_interfaces(&f, &sample_interfaces()).is_err());
+    }
+
+    #[test]
+    fn validate_filter_rejects_bad_subnet() {
+        assert!(validate_filter(&filter(InterfaceFilterMode::Allow, &["10.0.0.0/33"])).is_err());
+        assert!(validate_filter(&filter(InterfaceFilterMode::Allow, &["nope/8"])).is_err());
+        assert!(validate_filter(&filter(InterfaceFilterMode::Allow, &[])).is_err());
+        assert!(validate_filter(&filter(InterfaceFilterMode::Deny, &["10.0.0.0/8"])).is_ok());
+    }
 }
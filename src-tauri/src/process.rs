@@ -0,0 +1,375 @@
+use crate::commands;
+use crate::error::AppError;
+use crate::logging;
+use crate::models::{LogLevel, ManagedProcess, RestartPolicy, ServiceConfig, ServiceStatus};
+use crate::state::AppState;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How often the waiter checks whether the process has exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// First restart delay; doubled for each quick successive exit.
+const RESTART_BASE: Duration = Duration::from_secs(1);
+
+const RESTART_MAX: Duration = Duration::from_secs(60);
+
+/// A process that ran at least this long resets the backoff.
+const STABLE_AFTER: Duration = Duration::from_secs(30);
+
+/// A running managed process.
+pub struct RunningProcess {
+    pub pid: u32,
+    child: Arc<Mutex<Child>>,
+    /// Restarts in quick succession before this one.
+    restarts: u32,
+    started: Instant,
+}
+
+impl RunningProcess {
+    pub(crate) fn new(child: Child, restarts: u32) -> Self {
+        RunningProcess {
+            pid: child.id(),
+            child: Arc::new(Mutex::new(child)),
+            restarts,
+            started: Instant::now(),
+        }
+    }
+}
+
+pub fn validate(process: &ManagedProcess) -> Result<(), AppError> {
+    if process.command.trim().is_empty() {
+        return Err(AppError::Config("Process command must not be empty".into()));
+    }
+    if let Some(key) = process
+        .env
+        .keys()
+        .find(|k| k.is_empty() || k.contains('=') || k.contains('\0'))
+    {
+        return Err(AppError::Config(format!(
+            "Invalid environment variable name '{}'",
+            key
+        )));
+    }
+    Ok(())
+}
+
+fn should_restart(policy: RestartPolicy, exit: Option<ExitStatus>) -> bool {
+    match policy {
+        RestartPolicy::Never => false,
+        RestartPolicy::OnFailure => !exit.is_some_and(|e| e.success()),
+        RestartPolicy::Always => true,
+    }
+}
+
+/// Delay before the restart following `restarts` quick exits.
+fn backoff(restarts: u32) -> Duration {
+    RESTART_BASE
+        .saturating_mul(2u32.saturating_pow(restarts))
+        .min(RESTART_MAX)
+}
+
+fn describe_exit(exit: Option<ExitStatus>) -> String {
+    match exit {
+        Some(e) => match e.code() {
+            Some(code) => format!("exit code {}", code),
+            None => "killed by a signal".to_string(),
+        },
+        None => "status unknown".to_string(),
+    }
+}
+
+/// Start the service's process unless it is already running.
+pub fn ensure_started(
+    app: &AppHandle,
+    state: &AppState,
+    svc: &ServiceConfig,
+) -> Result<(), AppError> {
+    if svc.process.is_none() || state.processes.lock().unwrap().contains_key(&svc.id) {
+        return Ok(());
+    }
+    start(app, state, svc, 0)
+}
+
+fn start(
+    app: &AppHandle,
+    state: &AppState,
+    svc: &ServiceConfig,
+    restarts: u32,
+) -> Result<(), AppError> {
+    let Some(process) = &svc.process else {
+        return Ok(());
+    };
+    let mut command = Command::new(&process.command);
+    command
+        .args(&process.args)
+        .envs(&process.env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(cwd) = &process.cwd {
+        command.current_dir(cwd);
+    }
+    let mut child = command
+        .spawn()
+        .map_err(|e| AppError::Process(format!("failed to run '{}': {}", process.command, e)))?;
+
+    let pid = child.id();
+    if let Some(stdout) = child.stdout.take() {
+        forward_output(app, svc, stdout, LogLevel::Info);
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_output(app, svc, stderr, LogLevel::Warn);
+    }
+
+    let running = RunningProcess::new(child, restarts);
+    let child = running.child.clone();
+    {
+        let mut processes = state.processes.lock().unwrap();
+        processes.insert(svc.id.clone(), running);
+    }
+    logging::append_log(
+        app,
+        state,
+        LogLevel::Info,
+        format!(
+            "Started '{}' for service '{}' (pid {})",
+            process.command, svc.name, pid
+        ),
+        Some(svc.id.clone()),
+    );
+
+    let app = app.clone();
+    let svc = svc.clone();
+    thread::spawn(move || {
+        let exit = loop {
+            thread::sleep(POLL_INTERVAL);
+            match child.lock().unwrap().try_wait() {
+                Ok(Some(status)) => break Some(status),
+                Ok(None) => {}
+                Err(_) => break None,
+            }
+        };
+        exited(&app, &svc, &child, exit);
+    });
+    Ok(())
+}
+
+/// Log each line the process writes, tagged with the service.
+fn forward_output(
+    app: &AppHandle,
+    svc: &ServiceConfig,
+    stream: impl Read + Send + 'static,
+    level: LogLevel,
+) {
+    let app = app.clone();
+    let id = svc.id.clone();
+    let name = svc.name.clone();
+    thread::spawn(move || {
+        let state = app.state::<AppState>();
+        for line in BufReader::new(stream).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            logging::append_log(
+                &app,
+                &state,
+                level,
+                format!("{}: {}", name, line),
+                Some(id.clone()),
+            );
+        }
+    });
+}
+
+/// Kill the service's process, if it has one. Only the direct child is
+/// killed; programs that fork their own workers should exit with it.
+pub fn stop(state: &AppState, id: &str) {
+    let running = {
+        let mut processes = state.processes.lock().unwrap();
+        processes.remove(id)
+    };
+    if let Some(running) = running {
+        let mut child = running.child.lock().unwrap();
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+pub fn stop_all(state: &AppState) {
+    let ids: Vec<String> = state.processes.lock().unwrap().keys().cloned().collect();
+    for id in &ids {
+        stop(state, id);
+    }
+}
+
+/// The process exited on its own: withdraw the advertisement and restart it
+/// if the policy says so.
+fn exited(
+    app: &AppHandle,
+    svc: &ServiceConfig,
+    child: &Arc<Mutex<Child>>,
+    exit: Option<ExitStatus>,
+) {
+    let state = app.state::<AppState>();
+    let running = {
+        let mut processes = state.processes.lock().unwrap();
+        match processes.get(&svc.id) {
+            Some(p) if Arc::ptr_eq(&p.child, child) => processes.remove(&svc.id),
+            // Stopped deliberately
+            _ => None,
+        }
+    };
+    let Some(running) = running else {
+        return;
+    };
+
+    let active = {
+        let statuses = state.statuses.lock().unwrap();
+        statuses.get(&svc.id).is_some_and(|s| s.is_active())
+    };
+    if active {
+        let _ = commands::unregister_on_daemon(&state, svc, false);
+    }
+    let success = exit.is_some_and(|e| e.success());
+    {
+        let mut statuses = state.statuses.lock().unwrap();
+        let status = if success {
+            ServiceStatus::Stopped
+        } else {
+            ServiceStatus::Error
+        };
+        statuses.insert(svc.id.clone(), status);
+    }
+    logging::append_log(
+        app,
+        &state,
+        if success {
+            LogLevel::Info
+        } else {
+            LogLevel::Error
+        },
+        format!(
+            "Process for service '{}' exited ({}), advertisement withdrawn",
+            svc.name,
+            describe_exit(exit)
+        ),
+        Some(svc.id.clone()),
+    );
+    if let Ok(views) = commands::build_views(&state) {
+        let _ = app.emit("services-changed", &views);
+    }
+
+    let policy = svc
+        .process
+        .as_ref()
+        .map_or(RestartPolicy::Never, |p| p.restart);
+    if !should_restart(policy, exit) || *state.shut_down.lock().unwrap() {
+        return;
+    }
+    let restarts = if running.started.elapsed() >= STABLE_AFTER {
+        0
+    } else {
+        running.restarts + 1
+    };
+    let delay = backoff(restarts);
+    logging::append_log(
+        app,
+        &state,
+        LogLevel::Info,
+        format!("Restarting service '{}' in {}s", svc.name, delay.as_secs()),
+        Some(svc.id.clone()),
+    );
+    thread::sleep(delay);
+    restart(app, &state, &svc.id, restarts);
+}
+
+/// Start the process again and re-publish, unless the service was stopped,
+/// removed or started some other way in the meantime.
+fn restart(app: &AppHandle, state: &AppState, id: &str, restarts: u32) {
+    if *state.shut_down.lock().unwrap() {
+        return;
+    }
+    let (svc, hostname) = {
+        let config = state.config.lock().unwrap();
+        let Some(svc) = config.services.iter().find(|s| s.id == id) else {
+            return;
+        };
+        (svc.clone(), config.effective_hostname().to_string())
+    };
+    let active = {
+        let statuses = state.statuses.lock().unwrap();
+        statuses.get(id).is_some_and(|s| s.is_active())
+    };
+    if !svc.enabled
+        || svc.process.is_none()
+        || active
+        || state.processes.lock().unwrap().contains_key(id)
+    {
+        return;
+    }
+
+    match start(app, state, &svc, restarts) {
+        Ok(()) => commands::try_register_service(app, state, &svc, &hostname),
+        Err(e) => logging::append_log(
+            app,
+            state,
+            LogLevel::Error,
+            format!("Failed to restart service '{}': {}", svc.name, e),
+            Some(svc.id.clone()),
+        ),
+    }
+    if let Ok(views) = commands::build_views(state) {
+        let _ = app.emit("services-changed", &views);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn process(command: &str) -> ManagedProcess {
+        ManagedProcess {
+            command: command.into(),
+            args: Vec::new(),
+            cwd: None,
+            env: BTreeMap::new(),
+            restart: RestartPolicy::Never,
+        }
+    }
+
+    #[test]
+    fn validate_rejects_empty_command_and_bad_env() {
+        assert!(validate(&process("npm")).is_ok());
+        assert!(validate(&process("  ")).is_err());
+
+        let mut bad_env = process("npm");
+        bad_env.env.insert("A=B".into(), "1".into());
+        assert!(validate(&bad_env).is_err());
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        assert_eq!(backoff(0), Duration::from_secs(1));
+        assert_eq!(backoff(3), Duration::from_secs(8));
+        assert_eq!(backoff(10), RESTART_MAX);
+        assert_eq!(backoff(u32::MAX), RESTART_MAX);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn restart_policy_follows_exit_status() {
+        let ok = Command::new("true").status().ok();
+        let failed = Command::new("false").status().ok();
+        assert!(!should_restart(RestartPolicy::Never, failed));
+        assert!(!should_restart(RestartPolicy::OnFailure, ok));
+        assert!(should_restart(RestartPolicy::OnFailure, failed));
+        assert!(should_restart(RestartPolicy::OnFailure, None));
+        assert!(should_restart(RestartPolicy::Always, ok));
+    }
+}
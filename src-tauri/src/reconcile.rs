@@ -0,0 +1,417 @@
+use crate::commands;
+use crate::error::AppError;
+use crate::logging;
+use crate::models::{LogLevel, RetryPolicy, ServiceConfig, ServiceStatus};
+use crate::process;
+use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How often due retries are looked for.
+const TICK: Duration = Duration::from_secs(1);
+
+/// How often published state is audited against the config. A mismatch has
+/// to be seen by two audits in a row before it is corrected, so that
+/// changes still in flight are not mistaken for drift.
+const AUDIT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Progress of retrying one service's failed registration.
+#[derive(Debug, Clone)]
+pub struct RetryState {
+    pub attempts: u32,
+    pub next_attempt: DateTime<Utc>,
+}
+
+#[derive(Default)]
+pub struct Reconciler {
+    /// Services whose last registration failed, keyed by service id.
+    pub retries: HashMap<String, RetryState>,
+    /// Mismatches seen by the previous audit.
+    suspects: HashSet<Mismatch>,
+    last_audit: Option<Instant>,
+}
+
+/// What started a reconcile pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    Timer,
+    /// The daemon saw a network address appear or go away.
+    InterfacesChanged,
+    /// Requested from the UI.
+    OnDemand,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Mismatch {
+    /// Marked running, but nothing is published.
+    Unpublished(String),
+    /// Published, but deleted or disabled.
+    Orphaned(String),
+    /// Process running for a deleted or disabled service.
+    StrayProcess(String),
+}
+
+pub fn validate(policy: &RetryPolicy) -> Result<(), AppError> {
+    if policy.initial_backoff_secs == 0 {
+        return Err(AppError::Config(
+            "Retry backoff must be at least 1 second".into(),
+        ));
+    }
+    if policy.max_backoff_secs < policy.initial_backoff_secs {
+        return Err(AppError::Config(
+            "Maximum retry backoff must not be shorter than the initial one".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Delay after the `attempts`-th consecutive failure.
+pub fn backoff(policy: &RetryPolicy, attempts: u32) -> Duration {
+    let factor = 2u64.saturating_pow(attempts.saturating_sub(1));
+    let secs = policy
+        .initial_backoff_secs
+        .saturating_mul(factor)
+        .min(policy.max_backoff_secs);
+    Duration::from_secs(secs)
+}
+
+/// Schedule another attempt after a registration of `svc` failed. Returns
+/// `None` once its policy allows no more retries.
+pub fn record_failure(state: &AppState, svc: &ServiceConfig) -> Option<RetryState> {
+    let policy = svc.retry.clone().unwrap_or_default();
+    let mut reconciler = state.reconciler.lock().unwrap();
+    let attempts = reconciler.retries.get(&svc.id).map_or(0, |r| r.attempts) + 1;
+    if policy.max_attempts.is_some_and(|max| attempts > max) {
+        reconciler.retries.remove(&svc.id);
+        return None;
+    }
+    let retry = RetryState {
+        attempts,
+        next_attempt: Utc::now() + backoff(&policy, attempts),
+    };
+    reconciler.retries.insert(svc.id.clone(), retry.clone());
+    Some(retry)
+}
+
+pub fn clear_retry(state: &AppState, id: &str) {
+    let mut reconciler = state.reconciler.lock().unwrap();
+    reconciler.retries.remove(id);
+}
+
+/// Bring one service to its desired state: published while enabled,
+/// withdrawn otherwise. Call after changing the config; together with
+/// [`withdraw`] and [`republish`] this is how services are started and
+/// stopped. Health checks, process bindings, schedules and managed
+/// processes only decide when an enabled service is on the wire, and leave
+/// failed registrations to the retries and audits below.
+pub fn converge(app: &AppHandle, state: &AppState, svc: &ServiceConfig, hostname: &str) {
+    let status = {
+        let statuses = state.statuses.lock().unwrap();
+        statuses.get(&svc.id).copied()
+    };
+    let active = status.is_some_and(|s| s.is_active());
+    if svc.enabled && !active {
+        clear_retry(state, &svc.id);
+        commands::try_register_service(app, state, svc, hostname);
+    } else if !svc.enabled && status.is_some_and(|s| s != ServiceStatus::Stopped) {
+        commands::try_unregister_service(state, svc);
+    }
+}
+
+/// Take a service off the wire and stop its process, before it is deleted
+/// or replaced.
+pub fn withdraw(state: &AppState, svc: &ServiceConfig) {
+    let stopped = {
+        let statuses = state.statuses.lock().unwrap();
+        statuses
+            .get(&svc.id)
+            .is_none_or(|s| *s == ServiceStatus::Stopped)
+    };
+    if !stopped {
+        commands::try_unregister_service(state, svc);
+    }
+}
+
+/// Register a published service again after a change its records cannot
+/// carry, such as another hostname or interface set. Its process keeps
+/// running, and a failed registration is retried like any other.
+pub fn republish(app: &AppHandle, state: &AppState, svc: &ServiceConfig, hostname: &str) {
+    let _ = commands::unregister_on_daemon(state, svc, false);
+    commands::try_register_service(app, state, svc, hostname);
+}
+
+/// Reconcile in the background until the app shuts down.
+pub fn spawn_reconciler(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        let state = app.state::<AppState>();
+        if *state.shut_down.lock().unwrap() {
+            break;
+        }
+        reconcile(&app, &state, Trigger::Timer);
+    });
+}
+
+/// Retry failed registrations and correct drift between the config and what
+/// is published. A timer pass only retries what its backoff allows and
+/// audits every `AUDIT_INTERVAL`; an interface change retries everything
+/// pending right away, and an on-demand pass also audits right away.
+/// Returns whether anything was done.
+pub fn reconcile(app: &AppHandle, state: &AppState, trigger: Trigger) -> bool {
+    let mut changed = retry_failed(app, state, trigger);
+    let on_demand = trigger == Trigger::OnDemand;
+    let audit_due = on_demand || {
+        let reconciler = state.reconciler.lock().unwrap();
+        match reconciler.last_audit {
+            Some(at) => at.elapsed() >= AUDIT_INTERVAL,
+            None => true,
+        }
+    };
+    if audit_due {
+        changed |= audit_published(app, state, on_demand);
+    }
+    if changed {
+        if let Ok(views) = commands::build_views(state) {
+            let _ = app.emit("services-changed", &views);
+        }
+    }
+    changed
+}
+
+fn retry_failed(app: &AppHandle, state: &AppState, trigger: Trigger) -> bool {
+    let retry_now = trigger != Trigger::Timer;
+    let now = Utc::now();
+    let due: Vec<(String, u32)> = {
+        let reconciler = state.reconciler.lock().unwrap();
+        reconciler
+            .retries
+            .iter()
+            .filter(|(_, r)| retry_now || r.next_attempt <= now)
+            .map(|(id, r)| (id.clone(), r.attempts))
+            .collect()
+    };
+    if due.is_empty() {
+        return false;
+    }
+
+    let (services, hostname) = {
+        let config = state.config.lock().unwrap();
+        let statuses = state.statuses.lock().unwrap();
+        let services: Vec<(ServiceConfig, u32)> = due
+            .iter()
+            .filter_map(|(id, attempts)| {
+                let svc = config.services.iter().find(|s| &s.id == id)?;
+                let retrying = statuses.get(id).copied() == Some(ServiceStatus::Retrying);
+                (svc.enabled && retrying).then(|| (svc.clone(), *attempts))
+            })
+            .collect();
+        (services, config.effective_hostname().to_string())
+    };
+    {
+        // Stopped, deleted or recovered some other way in the meantime
+        let mut reconciler = state.reconciler.lock().unwrap();
+        for (id, _) in &due {
+            if !services.iter().any(|(s, _)| &s.id == id) {
+                reconciler.retries.remove(id);
+            }
+        }
+    }
+
+    if trigger == Trigger::InterfacesChanged && !services.is_empty() {
+        logging::append_log(
+            app,
+            state,
+            LogLevel::Info,
+            "Network interfaces changed, retrying failed services now".to_string(),
+            None,
+        );
+    }
+    for (svc, attempts) in &services {
+        let attempt = match svc.retry.as_ref().and_then(|p| p.max_attempts) {
+            Some(max) => format!("attempt {} of {}", attempts, max),
+            None => format!("attempt {}", attempts),
+        };
+        logging::append_log(
+            app,
+            state,
+            LogLevel::Info,
+            format!("Retrying service '{}' ({})", svc.name, attempt),
+            Some(svc.id.clone()),
+        );
+        commands::try_register_service(app, state, svc, &hostname);
+    }
+    !services.is_empty()
+}
+
+/// Compare the config with statuses, published records and processes, and
+/// correct mismatches seen by two audits in a row, or all of them when
+/// `immediate` is set.
+fn audit_published(app: &AppHandle, state: &AppState, immediate: bool) -> bool {
+    let found = find_mismatches(state);
+    let confirmed: Vec<Mismatch> = {
+        let mut reconciler = state.reconciler.lock().unwrap();
+        reconciler.last_audit = Some(Instant::now());
+        let confirmed = found
+            .iter()
+            .filter(|m| immediate || reconciler.suspects.contains(*m))
+            .cloned()
+            .collect();
+        reconciler.suspects = found;
+        confirmed
+    };
+
+    let hostname = {
+        let config = state.config.lock().unwrap();
+        config.effective_hostname().to_string()
+    };
+    for mismatch in &confirmed {
+        correct(app, state, mismatch, &hostname);
+    }
+    if !confirmed.is_empty() {
+        let mut reconciler = state.reconciler.lock().unwrap();
+        for mismatch in &confirmed {
+            reconciler.suspects.remove(mismatch);
+        }
+    }
+    !confirmed.is_empty()
+}
+
+fn find_mismatches(state: &AppState) -> HashSet<Mismatch> {
+    let config = state.config.lock().unwrap();
+    let statuses = state.statuses.lock().unwrap();
+    let published = state.published.lock().unwrap();
+    let processes = state.processes.lock().unwrap();
+    let enabled = |id: &str| config.services.iter().any(|s| s.id == id && s.enabled);
+
+    let mut found = HashSet::new();
+    for svc in &config.services {
+        if svc.enabled
+            && statuses.get(&svc.id).copied() == Some(ServiceStatus::Running)
+            && !published.contains_key(&svc.id)
+        {
+            found.insert(Mismatch::Unpublished(svc.id.clone()));
+        }
+    }
+    for id in published.keys().filter(|id| !enabled(id)) {
+        found.insert(Mismatch::Orphaned(id.clone()));
+    }
+    for id in processes.keys().filter(|id| !enabled(id)) {
+        found.insert(Mismatch::StrayProcess(id.clone()));
+    }
+    found
+}
+
+fn correct(app: &AppHandle, state: &AppState, mismatch: &Mismatch, hostname: &str) {
+    let lookup = |id: &str| {
+        let config = state.config.lock().unwrap();
+        config.services.iter().find(|s| s.id == id).cloned()
+    };
+    match mismatch {
+        Mismatch::Unpublished(id) => {
+            let Some(svc) = lookup(id) else {
+                return;
+            };
+            logging::append_log(
+                app,
+                state,
+                LogLevel::Warn,
+                format!(
+                    "Service '{}' was marked running but not published, registering it again",
+                    svc.name
+                ),
+                Some(svc.id.clone()),
+            );
+            commands::try_register_service(app, state, &svc, hostname);
+        }
+        Mismatch::Orphaned(id) => {
+            let record = {
+                let published = state.published.lock().unwrap();
+                published.get(id).cloned()
+            };
+            let Some(record) = record else {
+                return;
+            };
+            let _ = commands::unregister_on_daemon(state, &record.config, false);
+            let known = lookup(id).is_some();
+            {
+                let mut statuses = state.statuses.lock().unwrap();
+                if known {
+                    statuses.insert(id.clone(), ServiceStatus::Stopped);
+                } else {
+                    statuses.remove(id);
+                }
+            }
+            logging::append_log(
+                app,
+                state,
+                LogLevel::Warn,
+                format!(
+                    "Withdrew '{}', which was published without being enabled",
+                    record.fullname
+                ),
+                known.then(|| id.clone()),
+            );
+        }
+        Mismatch::StrayProcess(id) => {
+            process::stop(state, id);
+            let (message, service_id) = match lookup(id) {
+                Some(svc) => (
+                    format!(
+                        "Stopped the process of service '{}', which is not enabled",
+                        svc.name
+                    ),
+                    Some(svc.id),
+                ),
+                None => (
+                    "Stopped a process left behind by a deleted service".to_string(),
+                    None,
+                ),
+            };
+            logging::append_log(app, state, LogLevel::Warn, message, service_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy::default();
+        assert_eq!(backoff(&policy, 1), Duration::from_secs(2));
+        assert_eq!(backoff(&policy, 2), Duration::from_secs(4));
+        assert_eq!(backoff(&policy, 5), Duration::from_secs(32));
+        assert_eq!(backoff(&policy, 9), Duration::from_secs(300));
+        assert_eq!(backoff(&policy, u32::MAX), Duration::from_secs(300));
+
+        let custom = RetryPolicy {
+            max_attempts: Some(3),
+            initial_backoff_secs: 5,
+            max_backoff_secs: 15,
+        };
+        assert_eq!(backoff(&custom, 1), Duration::from_secs(5));
+        assert_eq!(backoff(&custom, 2), Duration::from_secs(10));
+        assert_eq!(backoff(&custom, 3), Duration::from_secs(15));
+    }
+
+    #[test]
+    fn validate_rejects_bad_backoff() {
+        assert!(validate(&RetryPolicy::default()).is_ok());
+        let zero = RetryPolicy {
+            initial_backoff_secs: 0,
+            ..RetryPolicy::default()
+        };
+        assert!(matches!(validate(&zero), Err(AppError::Config(_))));
+        let inverted = RetryPolicy {
+            initial_backoff_secs: 60,
+            max_backoff_secs: 30,
+            ..RetryPolicy::default()
+        };
+        assert!(validate(&inverted).is_err());
+    }
+}
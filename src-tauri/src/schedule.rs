@@ -0,0 +1,332 @@
+use crate::commands;
+use crate::config::save_config;
+use crate::error::AppError;
+use crate::logging;
+use crate::models::{AppConfig, LogLevel, Schedule, ServiceConfig, ServiceStatus};
+use crate::reconcile;
+use crate::state::AppState;
+use chrono::{
+    DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How often time limits and schedules are checked.
+const TICK: Duration = Duration::from_secs(1);
+
+fn parse_time(value: &str) -> Result<NaiveTime, AppError> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map_err(|_| AppError::Config(format!("Invalid time '{}', expected HH:MM", value)))
+}
+
+pub fn validate(schedule: &Schedule) -> Result<(), AppError> {
+    let start = parse_time(&schedule.start)?;
+    let end = parse_time(&schedule.end)?;
+    if start == end {
+        return Err(AppError::Config(
+            "Schedule must not start and end at the same time".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Whether the window is open at local time `at`. An invalid schedule is
+/// never open.
+pub fn is_open(schedule: &Schedule, at: NaiveDateTime) -> bool {
+    let (Ok(start), Ok(end)) = (parse_time(&schedule.start), parse_time(&schedule.end)) else {
+        return false;
+    };
+    let on = |day| schedule.days.is_empty() || schedule.days.contains(&day);
+    let time = at.time();
+    let today = at.weekday();
+    if start < end {
+        on(today) && time >= start && time < end
+    } else {
+        // Runs past midnight: opened today, or opened yesterday and not over
+        (on(today) && time >= start) || (on(today.pred()) && time < end)
+    }
+}
+
+/// The next local time after `after` at which the window opens or closes.
+pub fn next_transition(schedule: &Schedule, after: NaiveDateTime) -> Option<NaiveDateTime> {
+    let start = parse_time(&schedule.start).ok()?;
+    let end = parse_time(&schedule.end).ok()?;
+    let open_now = is_open(schedule, after);
+    (0..=8)
+        .map(|offset| after.date() + ChronoDuration::days(offset))
+        .flat_map(|date| {
+            let mut times = [date.and_time(start), date.and_time(end)];
+            times.sort();
+            times
+        })
+        .filter(|candidate| *candidate > after)
+        .find(|candidate| is_open(schedule, *candidate) != open_now)
+}
+
+fn to_utc(local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&local)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+pub fn is_open_now(schedule: &Schedule) -> bool {
+    is_open(schedule, Local::now().naive_local())
+}
+
+pub fn next_transition_utc(schedule: &Schedule) -> Option<DateTime<Utc>> {
+    next_transition(schedule, Local::now().naive_local()).and_then(to_utc)
+}
+
+fn describe(at: Option<DateTime<Utc>>) -> String {
+    match at {
+        Some(at) => at.with_timezone(&Local).format("%a %H:%M").to_string(),
+        None => "never".to_string(),
+    }
+}
+
+/// Drive time limits and schedules in the background until the app shuts
+/// down.
+pub fn spawn_scheduler(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        let state = app.state::<AppState>();
+        if *state.shut_down.lock().unwrap() {
+            break;
+        }
+        let expired = stop_expired(&app, &state);
+        let transitioned = apply_schedules(&app, &state);
+        if expired || transitioned {
+            if let Ok(views) = commands::build_views(&state) {
+                let _ = app.emit("services-changed", &views);
+            }
+        }
+    });
+}
+
+/// Disable services whose time limit ran out while the app was not
+/// running, before anything is published. Returns whether any was.
+pub fn disable_expired(config: &mut AppConfig, now: DateTime<Utc>) -> bool {
+    let mut changed = false;
+    for svc in &mut config.services {
+        if svc.stop_at.is_some_and(|at| at <= now) {
+            svc.enabled = false;
+            svc.stop_at = None;
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Stop and disable services whose time limit has passed.
+fn stop_expired(app: &AppHandle, state: &AppState) -> bool {
+    let now = Utc::now();
+    let expired: Vec<String> = {
+        let stop_at = state.stop_at.lock().unwrap();
+        stop_at
+            .iter()
+            .filter(|(_, at)| **at <= now)
+            .map(|(id, _)| id.clone())
+            .collect()
+    };
+    if expired.is_empty() {
+        return false;
+    }
+
+    for id in &expired {
+        let svc = {
+            let mut config = state.config.lock().unwrap();
+            let Some(svc) = config.services.iter_mut().find(|s| &s.id == id) else {
+                state.stop_at.lock().unwrap().remove(id);
+                continue;
+            };
+            svc.enabled = false;
+            svc.stop_at = None;
+            let svc = svc.clone();
+            let _ = save_config(&config);
+            svc
+        };
+        commands::try_unregister_service(state, &svc);
+        logging::append_log(
+            app,
+            state,
+            LogLevel::Info,
+            format!(
+                "Service '{}' stopped at the end of its time limit",
+                svc.name
+            ),
+            Some(svc.id.clone()),
+        );
+    }
+    true
+}
+
+/// Publish scheduled services whose window opened and withdraw the records
+/// of those whose window closed. Their processes keep running.
+fn apply_schedules(app: &AppHandle, state: &AppState) -> bool {
+    let (candidates, hostname) = {
+        let config = state.config.lock().unwrap();
+        let statuses = state.statuses.lock().unwrap();
+        let candidates: Vec<(ServiceConfig, ServiceStatus)> = config
+            .services
+            .iter()
+            .filter(|s| s.enabled && s.schedule.is_some())
+            .filter_map(|s| {
+                let status = statuses.get(&s.id).copied()?;
+                status.is_active().then(|| (s.clone(), status))
+            })
+            .collect();
+        (candidates, config.effective_hostname().to_string())
+    };
+
+    let mut changed = false;
+    for (svc, status) in &candidates {
+        let Some(schedule) = &svc.schedule else {
+            continue;
+        };
+        let open = is_open_now(schedule);
+        if open && *status == ServiceStatus::Scheduled {
+            logging::append_log(
+                app,
+                state,
+                LogLevel::Info,
+                format!(
+                    "Schedule for service '{}' opened until {}",
+                    svc.name,
+                    describe(next_transition_utc(schedule))
+                ),
+                Some(svc.id.clone()),
+            );
+            commands::try_register_service(app, state, svc, &hostname);
+            changed = true;
+        } else if !open && *status != ServiceStatus::Scheduled {
+            // Only the record goes; the process stays up for the next window
+            let _ = commands::unregister_on_daemon(state, svc, false);
+            reconcile::clear_retry(state, &svc.id);
+            {
+                let mut statuses = state.statuses.lock().unwrap();
+                statuses.insert(svc.id.clone(), ServiceStatus::Scheduled);
+            }
+            logging::append_log(
+                app,
+                state,
+                LogLevel::Info,
+                format!(
+                    "Schedule for service '{}' closed, withdrawn until {}",
+                    svc.name,
+                    describe(next_transition_utc(schedule))
+                ),
+                Some(svc.id.clone()),
+            );
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Log line for a service that was started outside its window.
+pub fn closed_message(svc: &ServiceConfig, schedule: &Schedule) -> String {
+    format!(
+        "Service '{}' is outside its schedule until {}",
+        svc.name,
+        describe(next_transition_utc(schedule))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Weekday};
+
+    fn schedule(days: Vec<Weekday>, start: &str, end: &str) -> Schedule {
+        Schedule {
+            days,
+            start: start.into(),
+            end: end.into(),
+        }
+    }
+
+    // 2026-10-19 is a Monday
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn weekdays() -> Vec<Weekday> {
+        vec![
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ]
+    }
+
+    #[test]
+    fn disable_expired_ends_lapsed_time_limits() {
+        let now = Utc::now();
+        let timed = |id: &str, stop_at| ServiceConfig {
+            id: id.into(),
+            enabled: true,
+            stop_at: Some(stop_at),
+            ..Default::default()
+        };
+        let mut config = AppConfig {
+            services: vec![
+                timed("lapsed", now - ChronoDuration::minutes(5)),
+                timed("pending", now + ChronoDuration::minutes(5)),
+            ],
+            ..Default::default()
+        };
+
+        assert!(disable_expired(&mut config, now));
+        assert!(!config.services[0].enabled);
+        assert!(config.services[0].stop_at.is_none());
+        assert!(config.services[1].enabled);
+        assert!(config.services[1].stop_at.is_some());
+        assert!(!disable_expired(&mut config, now));
+    }
+
+    #[test]
+    fn validate_rejects_bad_times() {
+        assert!(validate(&schedule(vec![], "09:00", "19:00")).is_ok());
+        assert!(validate(&schedule(vec![], "9am", "19:00")).is_err());
+        assert!(validate(&schedule(vec![], "25:00", "19:00")).is_err());
+        assert!(validate(&schedule(vec![], "09:00", "09:00")).is_err());
+    }
+
+    #[test]
+    fn weekday_window() {
+        let s = schedule(weekdays(), "09:00", "19:00");
+        assert!(is_open(&s, at(19, 9, 0)));
+        assert!(is_open(&s, at(23, 18, 59)));
+        assert!(!is_open(&s, at(19, 19, 0)));
+        assert!(!is_open(&s, at(19, 8, 59)));
+        // Saturday
+        assert!(!is_open(&s, at(24, 12, 0)));
+    }
+
+    #[test]
+    fn window_past_midnight_belongs_to_start_day() {
+        let s = schedule(vec![Weekday::Fri], "22:00", "02:00");
+        assert!(is_open(&s, at(23, 23, 0)));
+        // Early Saturday, still Friday's window
+        assert!(is_open(&s, at(24, 1, 59)));
+        assert!(!is_open(&s, at(24, 2, 0)));
+        // Early Friday belongs to Thursday, which is not scheduled
+        assert!(!is_open(&s, at(23, 1, 0)));
+    }
+
+    #[test]
+    fn next_transition_skips_closed_days() {
+        let s = schedule(weekdays(), "09:00", "19:00");
+        assert_eq!(next_transition(&s, at(19, 12, 0)), Some(at(19, 19, 0)));
+        assert_eq!(next_transition(&s, at(19, 19, 0)), Some(at(20, 9, 0)));
+        // Friday evening -> Monday morning
+        assert_eq!(next_transition(&s, at(23, 20, 0)), Some(at(26, 9, 0)));
+    }
+}
@@ -1,11 +1,110 @@
-use crate::models::{AppConfig, LogEntry, ServiceStatus};
+use crate::health::HealthState;
+use crate::models::{
+    AppConfig, DiscoveredService, LogEntry, PublishedRecord, ServiceStatus, Verification,
+};
+use crate::process::RunningProcess;
+use crate::reconcile::Reconciler;
+use crate::txt_source::SourcedValue;
+use crate::verify::Verifier;
+use chrono::{DateTime, Utc};
 use mdns_sd::ServiceDaemon;
-use std::collections::{HashMap, VecDeque};
-use std::sync::Mutex;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::{mpsc, Mutex};
+use std::time::Instant;
+
+/// A service type seen while enumerating the network.
+pub struct TypeSighting {
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    /// Instance fullnames currently announced under the type.
+    pub instances: HashSet<String>,
+}
+
+/// A caller of `browse::resolve_instance` waiting for an instance.
+pub struct ResolveWaiter {
+    pub token: u64,
+    /// Lowercase instance fullname.
+    pub fullname: String,
+    pub sender: mpsc::Sender<DiscoveredService>,
+}
+
+/// Browsing and type enumeration, which share one daemon so that a type is
+/// only ever queried once.
+#[derive(Default)]
+pub struct Browser {
+    /// Created on first use.
+    pub daemon: Option<ServiceDaemon>,
+    /// Fully qualified types with a running query.
+    pub queries: HashSet<String>,
+    /// Types the user is browsing; their results stream as `browse-event`.
+    pub shown: HashSet<String>,
+    /// Resolved instances per queried type, keyed by fullname.
+    pub resolved: HashMap<String, BTreeMap<String, DiscoveredService>>,
+    /// Types seen on the network, or `None` while enumeration is off.
+    pub types: Option<BTreeMap<String, TypeSighting>>,
+    /// Types queried for the watchlist.
+    pub watched: HashSet<String>,
+    /// Last resolved state of watched instances, keyed by lowercase fullname.
+    pub watch_seen: HashMap<String, DiscoveredService>,
+    /// Newly watched types and the end of their first snapshot; instances
+    /// resolved before then are recorded without an alert.
+    pub watch_priming: HashMap<String, Instant>,
+    /// Callers waiting for an instance to resolve.
+    pub resolving: Vec<ResolveWaiter>,
+    pub next_token: u64,
+}
+
+impl Browser {
+    /// Whether a query for `ty` is still needed by browsing, enumeration or
+    /// the watchlist.
+    pub fn in_use(&self, ty: &str) -> bool {
+        self.shown.contains(ty) || self.types.is_some() || self.watched.contains(ty)
+    }
+}
 
 pub struct AppState {
     pub config: Mutex<AppConfig>,
     pub daemon: Mutex<ServiceDaemon>,
     pub statuses: Mutex<HashMap<String, ServiceStatus>>,
+    /// Records actually on the wire, keyed by service id.
+    pub published: Mutex<HashMap<String, PublishedRecord>>,
+    /// Self-verification results for published services, keyed by service id.
+    pub verifications: Mutex<HashMap<String, Verification>>,
+    /// Browses that running verifications wait on.
+    pub verifier: Mutex<Verifier>,
+    /// Health check progress for active services with a check, keyed by service id.
+    pub health: Mutex<HashMap<String, HealthState>>,
+    /// Managed child processes, keyed by service id.
+    pub processes: Mutex<HashMap<String, RunningProcess>>,
+    /// End of time-limited starts, keyed by service id.
+    pub stop_at: Mutex<HashMap<String, DateTime<Utc>>>,
+    /// Pending retries and audit progress of the reconciler.
+    pub reconciler: Mutex<Reconciler>,
+    /// Values read from TXT sources, keyed by service id and then TXT key.
+    pub txt_values: Mutex<HashMap<String, HashMap<String, SourcedValue>>>,
+    pub browser: Mutex<Browser>,
     pub logs: Mutex<VecDeque<LogEntry>>,
+    /// Set once the daemon has been shut down so later exit paths are no-ops.
+    pub shut_down: Mutex<bool>,
+}
+
+impl AppState {
+    pub fn new(config: AppConfig, daemon: ServiceDaemon) -> Self {
+        AppState {
+            config: Mutex::new(config),
+            daemon: Mutex::new(daemon),
+            statuses: Mutex::new(HashMap::new()),
+            published: Mutex::new(HashMap::new()),
+            verifications: Mutex::new(HashMap::new()),
+            verifier: Mutex::new(Verifier::default()),
+            health: Mutex::new(HashMap::new()),
+            processes: Mutex::new(HashMap::new()),
+            stop_at: Mutex::new(HashMap::new()),
+            reconciler: Mutex::new(Reconciler::default()),
+            txt_values: Mutex::new(HashMap::new()),
+            browser: Mutex::new(Browser::default()),
+            logs: Mutex::new(VecDeque::new()),
+            shut_down: Mutex::new(false),
+        }
+    }
 }
@@ -0,0 +1,192 @@
+use crate::error::AppError;
+use crate::models::{ServiceConfig, TxtEntry};
+use crate::network;
+use std::net::IpAddr;
+
+/// A `{{...}}` placeholder in an instance name or TXT value.
+#[derive(Debug, PartialEq, Eq)]
+enum Placeholder<'a> {
+    /// The hostname services are advertised under.
+    Hostname,
+    /// The user running the app.
+    User,
+    /// First address of a network interface, IPv4 preferred.
+    Ip(&'a str),
+    Env(&'a str),
+    /// File contents without the trailing newline.
+    File(&'a str),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Segment<'a> {
+    Literal(&'a str),
+    Placeholder(Placeholder<'a>),
+}
+
+fn parse_placeholder(inner: &str) -> Result<Placeholder<'_>, AppError> {
+    let inner = inner.trim();
+    let (name, arg) = match inner.split_once(':') {
+        Some((name, arg)) => (name.trim(), Some(arg.trim())),
+        None => (inner, None),
+    };
+    let placeholder = match (name, arg) {
+        ("hostname", None) => Placeholder::Hostname,
+        ("user", None) => Placeholder::User,
+        ("ip", Some(iface)) if !iface.is_empty() => Placeholder::Ip(iface),
+        ("env", Some(var)) if !var.is_empty() => Placeholder::Env(var),
+        ("file", Some(path)) if !path.is_empty() => Placeholder::File(path),
+        _ => {
+            return Err(AppError::Template(format!(
+                "unknown placeholder '{{{{{}}}}}'",
+                inner
+            )))
+        }
+    };
+    Ok(placeholder)
+}
+
+fn segments(text: &str) -> Result<Vec<Segment<'_>>, AppError> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+    while let Some(open) = rest.find("{{") {
+        if open > 0 {
+            segments.push(Segment::Literal(&rest[..open]));
+        }
+        let after = &rest[open + 2..];
+        let close = after
+            .find("}}")
+            .ok_or_else(|| AppError::Template(format!("unclosed '{{{{' in '{}'", text)))?;
+        segments.push(Segment::Placeholder(parse_placeholder(&after[..close])?));
+        rest = &after[close + 2..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest));
+    }
+    Ok(segments)
+}
+
+/// Check placeholder syntax without resolving anything.
+pub fn validate(text: &str) -> Result<(), AppError> {
+    segments(text).map(|_| ())
+}
+
+/// Check the instance name and text TXT values of a service.
+pub fn validate_service(name: &str, txt: &[TxtEntry]) -> Result<(), AppError> {
+    validate(name)?;
+    txt.iter()
+        .filter(|e| !e.binary)
+        .filter_map(|e| e.value.as_deref())
+        .try_for_each(validate)
+}
+
+fn resolve(placeholder: &Placeholder, hostname: &str) -> Result<String, AppError> {
+    match placeholder {
+        Placeholder::Hostname => Ok(hostname.to_string()),
+        Placeholder::User => std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .map_err(|_| AppError::Template("cannot determine the current user".into())),
+        Placeholder::Ip(iface) => {
+            let addresses: Vec<IpAddr> = network::get_interfaces()
+                .into_iter()
+                .find(|i| i.name == *iface)
+                .ok_or_else(|| AppError::Template(format!("no interface named '{}'", iface)))?
+                .addresses
+                .iter()
+                .filter_map(|a| a.parse().ok())
+                .collect();
+            addresses
+                .iter()
+                .find(|a| a.is_ipv4())
+                .or_else(|| addresses.first())
+                .map(IpAddr::to_string)
+                .ok_or_else(|| AppError::Template(format!("interface '{}' has no address", iface)))
+        }
+        Placeholder::Env(var) => std::env::var(var)
+            .map_err(|_| AppError::Template(format!("environment variable '{}' is not set", var))),
+        Placeholder::File(path) => std::fs::read_to_string(path)
+            .map(|contents| contents.trim_end_matches(['\r', '\n']).to_string())
+            .map_err(|e| AppError::Template(format!("cannot read '{}': {}", path, e))),
+    }
+}
+
+/// Expand every placeholder in `text`.
+pub fn render(text: &str, hostname: &str) -> Result<String, AppError> {
+    let mut rendered = String::with_capacity(text.len());
+    for segment in segments(text)? {
+        match segment {
+            Segment::Literal(literal) => rendered.push_str(literal),
+            Segment::Placeholder(p) => rendered.push_str(&resolve(&p, hostname)?),
+        }
+    }
+    Ok(rendered)
+}
+
+/// `svc` with the instance name and text TXT values expanded. Values read
+/// from a TXT source are published verbatim.
+pub fn render_service(svc: &ServiceConfig, hostname: &str) -> Result<ServiceConfig, AppError> {
+    let mut rendered = svc.clone();
+    rendered.name = render(&svc.name, hostname)?;
+    let sourced = |key: &str| {
+        svc.txt_sources
+            .iter()
+            .any(|s| s.key.eq_ignore_ascii_case(key))
+    };
+    for entry in rendered
+        .txt
+        .iter_mut()
+        .filter(|e| !e.binary && !sourced(&e.key))
+    {
+        if let Some(value) = &entry.value {
+            entry.value = Some(render(value, hostname)?);
+        }
+    }
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn segments_split_literals_and_placeholders() {
+        assert_eq!(
+            segments("Dev ({{ hostname }}) on {{ip:eth0}}").unwrap(),
+            vec![
+                Segment::Literal("Dev ("),
+                Segment::Placeholder(Placeholder::Hostname),
+                Segment::Literal(") on "),
+                Segment::Placeholder(Placeholder::Ip("eth0")),
+            ]
+        );
+        assert_eq!(segments("plain").unwrap(), vec![Segment::Literal("plain")]);
+    }
+
+    #[test]
+    fn validate_rejects_unknown_and_unclosed() {
+        assert!(validate("{{user}}'s server").is_ok());
+        assert!(matches!(validate("{{color}}"), Err(AppError::Template(_))));
+        assert!(validate("{{env:}}").is_err());
+        assert!(validate("{{hostname").is_err());
+    }
+
+    #[test]
+    fn render_expands_hostname_env_and_file() {
+        std::env::set_var("NOROSHI_TEMPLATE_TEST", "blue");
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "v1.2.3").unwrap();
+        let text = format!(
+            "{{{{hostname}}}}-{{{{env:NOROSHI_TEMPLATE_TEST}}}}-{{{{file:{}}}}}",
+            file.path().display()
+        );
+        assert_eq!(render(&text, "devbox").unwrap(), "devbox-blue-v1.2.3");
+    }
+
+    #[test]
+    fn render_reports_missing_values() {
+        let err = render("{{env:NOROSHI_TEMPLATE_UNSET}}", "devbox").unwrap_err();
+        assert!(err.to_string().contains("NOROSHI_TEMPLATE_UNSET"));
+        assert!(render("{{ip:no-such-iface0}}", "devbox").is_err());
+        assert!(render("{{file:/nonexistent/noroshi}}", "devbox").is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::error::AppError;
+use crate::models::TxtEntry;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use mdns_sd::TxtProperty;
+use std::collections::HashSet;
+
+/// A single TXT string (`key=value`) is prefixed by a one-byte length.
+const MAX_STRING_LEN: usize = 255;
+
+/// RFC 6763 section 6.2: the whole TXT record should not exceed 8900 bytes.
+const MAX_RECORD_LEN: usize = 8900;
+
+/// Raw bytes of an entry's value, decoding base64 for binary entries.
+fn value_bytes(entry: &TxtEntry) -> Result<Option<Vec<u8>>, AppError> {
+    match &entry.value {
+        None => Ok(None),
+        Some(v) if entry.binary => BASE64
+            .decode(v)
+            .map(Some)
+            .map_err(|e| AppError::Txt(format!("'{}' is not valid base64: {}", entry.key, e))),
+        Some(v) => Ok(Some(v.as_bytes().to_vec())),
+    }
+}
+
+/// Check keys and the per-string and total size limits.
+pub fn validate(entries: &[TxtEntry]) -> Result<(), AppError> {
+    let mut seen = HashSet::new();
+    let mut total = 0;
+
+    for entry in entries {
+        let key = &entry.key;
+        if key.is_empty() {
+            return Err(AppError::Txt("key is empty".into()));
+        }
+        // Keys are printable US-ASCII excluding '=' (RFC 6763 section 6.4)
+        if !key.bytes().all(|b| (0x20..=0x7e).contains(&b) && b != b'=') {
+            return Err(AppError::Txt(format!(
+                "key '{}' must be printable ASCII without '='",
+                key
+            )));
+        }
+        if !seen.insert(key.to_ascii_lowercase()) {
+            return Err(AppError::Txt(format!("duplicate key '{}'", key)));
+        }
+
+        let len = match value_bytes(entry)? {
+            Some(bytes) => key.len() + 1 + bytes.len(),
+            None => key.len(),
+        };
+        if len > MAX_STRING_LEN {
+            return Err(AppError::Txt(format!(
+                "'{}' is {} bytes, the limit per entry is {}",
+                key, len, MAX_STRING_LEN
+            )));
+        }
+        total += len + 1;
+    }
+
+    if total > MAX_RECORD_LEN {
+        return Err(AppError::Txt(format!(
+            "record is {} bytes, the limit is {}",
+            total, MAX_RECORD_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Convert entries into mdns-sd properties, keeping their order.
+pub fn to_properties(entries: &[TxtEntry]) -> Result<Vec<TxtProperty>, AppError> {
+    validate(entries)?;
+    entries
+        .iter()
+        .map(|entry| {
+            Ok(match value_bytes(entry)? {
+                Some(bytes) => TxtProperty::from((entry.key.as_str(), bytes)),
+                None => TxtProperty::from(entry.key.as_str()),
+            })
+        })
+        .collect()
+}
+
+/// Convert a received property back into an entry. Values that are not
+/// valid UTF-8 come back as base64-encoded binary entries.
+pub fn from_property(property: &TxtProperty) -> TxtEntry {
+    let (value, binary) = match property.val() {
+        None => (None, false),
+        Some(bytes) => match std::str::from_utf8(bytes) {
+            Ok(text) => (Some(text.to_string()), false),
+            Err(_) => (Some(BASE64.encode(bytes)), true),
+        },
+    };
+    TxtEntry {
+        key: property.key().to_string(),
+        value,
+        binary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flag(key: &str) -> TxtEntry {
+        TxtEntry {
+            key: key.into(),
+            value: None,
+            binary: false,
+        }
+    }
+
+    fn binary(key: &str, b64: &str) -> TxtEntry {
+        TxtEntry {
+            key: key.into(),
+            value: Some(b64.into()),
+            binary: true,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_mixed_entries() {
+        let entries = vec![
+            TxtEntry::text("path", "/"),
+            TxtEntry::text("empty", ""),
+            flag("secure"),
+            binary("blob", "AAEC/w=="),
+        ];
+        assert!(validate(&entries).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_bad_keys() {
+        assert!(validate(&[TxtEntry::text("", "x")]).is_err());
+        assert!(validate(&[TxtEntry::text("a=b", "x")]).is_err());
+        assert!(validate(&[TxtEntry::text("caf\u{e9}", "x")]).is_err());
+        assert!(validate(&[TxtEntry::text("Path", "/"), flag("path")]).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_invalid_base64() {
+        let err = validate(&[binary("blob", "not base64!")]).unwrap_err();
+        assert!(err.to_string().starts_with("TXT record error:"));
+    }
+
+    #[test]
+    fn validate_enforces_string_limit() {
+        // "k=" plus 253 bytes is exactly 255
+        assert!(validate(&[TxtEntry::text("k", "v".repeat(253))]).is_ok());
+        assert!(validate(&[TxtEntry::text("k", "v".repeat(254))]).is_err());
+    }
+
+    #[test]
+    fn validate_enforces_total_limit() {
+        let entries: Vec<TxtEntry> = (0..40)
+            .map(|i| TxtEntry::text(format!("key{:02}", i), "v".repeat(240)))
+            .collect();
+        assert!(validate(&entries).is_err());
+    }
+
+    #[test]
+    fn to_properties_keeps_order_and_kinds() {
+        let entries = vec![TxtEntry::text("z", "1"), flag("a"), binary("bin", "AP8=")];
+        let props = to_properties(&entries).unwrap();
+        let keys: Vec<&str> = props.iter().map(|p| p.key()).collect();
+        assert_eq!(keys, vec!["z", "a", "bin"]);
+        assert_eq!(props[0].val(), Some(&b"1"[..]));
+        assert_eq!(props[1].val(), None);
+        assert_eq!(props[2].val(), Some(&[0x00, 0xff][..]));
+    }
+
+    #[test]
+    fn from_property_round_trips_kinds() {
+        let entries = vec![
+            TxtEntry::text("path", "/"),
+            flag("secure"),
+            binary("bin", "AP8="),
+        ];
+        let back: Vec<TxtEntry> = to_properties(&entries)
+            .unwrap()
+            .iter()
+            .map(from_property)
+            .collect();
+        assert_eq!(back, entries);
+    }
+}
@@ -0,0 +1,380 @@
+use crate::commands;
+use crate::error::AppError;
+use crate::logging;
+use crate::models::{LogLevel, ServiceConfig, ServiceStatus, TxtEntry, TxtSource, TxtSourceKind};
+use crate::state::AppState;
+use crate::txt;
+use std::collections::HashSet;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How often sources are checked for being due.
+const TICK: Duration = Duration::from_secs(1);
+
+/// A command that takes longer than this is killed and counts as a failure.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Last good value of one source.
+pub struct SourcedValue {
+    /// `None` until the source has been read successfully.
+    pub value: Option<String>,
+    pub last_run: Instant,
+}
+
+pub fn validate(sources: &[TxtSource]) -> Result<(), AppError> {
+    let mut seen = HashSet::new();
+    for source in sources {
+        let key = &source.key;
+        if key.is_empty() || !key.bytes().all(|b| (0x20..=0x7e).contains(&b) && b != b'=') {
+            return Err(AppError::Txt(format!(
+                "source key '{}' must be printable ASCII without '='",
+                key
+            )));
+        }
+        if !seen.insert(key.to_ascii_lowercase()) {
+            return Err(AppError::Txt(format!("duplicate source key '{}'", key)));
+        }
+        if source.interval_secs == 0 {
+            return Err(AppError::Txt(format!(
+                "refresh interval of '{}' must be at least 1 second",
+                key
+            )));
+        }
+        let empty = match &source.kind {
+            TxtSourceKind::Command { command } => command.trim().is_empty(),
+            TxtSourceKind::File { path } => path.trim().is_empty(),
+        };
+        if empty {
+            return Err(AppError::Txt(format!("source of '{}' is empty", key)));
+        }
+    }
+    Ok(())
+}
+
+/// Read the current value of a source. Values must fit in one TXT string.
+pub fn fetch(source: &TxtSource) -> Result<String, AppError> {
+    let value = match &source.kind {
+        TxtSourceKind::Command { command } => run_command(command)?,
+        TxtSourceKind::File { path } => std::fs::read_to_string(path)
+            .map_err(|e| AppError::Txt(format!("cannot read '{}': {}", path, e)))?,
+    };
+    let value = value.trim().to_string();
+    txt::validate(&[TxtEntry {
+        key: source.key.clone(),
+        value: Some(value.clone()),
+        binary: false,
+    }])?;
+    Ok(value)
+}
+
+fn run_command(command: &str) -> Result<String, AppError> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    };
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| AppError::Txt(format!("cannot run '{}': {}", command, e)))?;
+
+    // Drain stdout on the side so a chatty command cannot fill the pipe
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let reader = thread::spawn(move || {
+        let mut output = Vec::new();
+        let _ = stdout.read_to_end(&mut output);
+        output
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= COMMAND_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AppError::Txt(format!(
+                    "'{}' did not finish within {}s",
+                    command,
+                    COMMAND_TIMEOUT.as_secs()
+                )));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(AppError::Txt(format!("'{}' failed: {}", command, e))),
+        }
+    };
+    let output = reader.join().unwrap_or_default();
+    if !status.success() {
+        return Err(AppError::Txt(format!(
+            "'{}' exited with {}",
+            command, status
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
+
+/// `txt` with sourced values in place of static entries with the same key;
+/// keys without a static entry are appended.
+pub fn merge(txt: &[TxtEntry], values: &[(String, String)]) -> Vec<TxtEntry> {
+    let mut merged = txt.to_vec();
+    for (key, value) in values {
+        let entry = TxtEntry {
+            key: key.clone(),
+            value: Some(value.clone()),
+            binary: false,
+        };
+        match merged.iter_mut().find(|e| e.key.eq_ignore_ascii_case(key)) {
+            Some(existing) => *existing = entry,
+            None => merged.push(entry),
+        }
+    }
+    merged
+}
+
+/// `svc` with the last good value of each of its sources. A source without
+/// one yet is read now; if that fails its key is left out.
+pub fn apply(state: &AppState, svc: &ServiceConfig) -> ServiceConfig {
+    if svc.txt_sources.is_empty() {
+        return svc.clone();
+    }
+    let mut values = Vec::new();
+    for source in &svc.txt_sources {
+        let known = {
+            let txt_values = state.txt_values.lock().unwrap();
+            txt_values
+                .get(&svc.id)
+                .and_then(|v| v.get(&source.key))
+                .and_then(|v| v.value.clone())
+        };
+        let value = match known {
+            Some(value) => Some(value),
+            None => fetch(source).ok().inspect(|value| {
+                store(state, &svc.id, &source.key, Some(value.clone()));
+            }),
+        };
+        if let Some(value) = value {
+            values.push((source.key.clone(), value));
+        }
+    }
+    let mut applied = svc.clone();
+    applied.txt = merge(&svc.txt, &values);
+    applied
+}
+
+fn store(state: &AppState, id: &str, key: &str, value: Option<String>) {
+    let mut txt_values = state.txt_values.lock().unwrap();
+    txt_values.entry(id.to_string()).or_default().insert(
+        key.to_string(),
+        SourcedValue {
+            value,
+            last_run: Instant::now(),
+        },
+    );
+}
+
+/// Refresh sourced TXT values in the background until the app shuts down.
+pub fn spawn_refresher(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        let state = app.state::<AppState>();
+        if *state.shut_down.lock().unwrap() {
+            break;
+        }
+        refresh_due_sources(&app, &state);
+    });
+}
+
+fn refresh_due_sources(app: &AppHandle, state: &AppState) {
+    let (candidates, hostname) = {
+        let config = state.config.lock().unwrap();
+        let statuses = state.statuses.lock().unwrap();
+        let candidates: Vec<(ServiceConfig, ServiceStatus)> = config
+            .services
+            .iter()
+            .filter(|s| !s.txt_sources.is_empty())
+            .filter_map(|s| {
+                let status = statuses.get(&s.id).copied()?;
+                status.is_active().then(|| (s.clone(), status))
+            })
+            .collect();
+        (candidates, config.effective_hostname().to_string())
+    };
+    {
+        // Forget values of services that stopped or lost their sources
+        let mut txt_values = state.txt_values.lock().unwrap();
+        txt_values.retain(
+            |id, values| match candidates.iter().find(|(s, _)| &s.id == id) {
+                Some((svc, _)) => {
+                    values.retain(|key, _| svc.txt_sources.iter().any(|t| &t.key == key));
+                    true
+                }
+                None => false,
+            },
+        );
+    }
+
+    let mut changed = false;
+    for (svc, status) in &candidates {
+        let mut updated = false;
+        for source in &svc.txt_sources {
+            let previous = {
+                let txt_values = state.txt_values.lock().unwrap();
+                txt_values
+                    .get(&svc.id)
+                    .and_then(|v| v.get(&source.key))
+                    .map(|v| (v.value.clone(), v.last_run))
+            };
+            let interval = Duration::from_secs(source.interval_secs);
+            if previous
+                .as_ref()
+                .is_some_and(|(_, last_run)| last_run.elapsed() < interval)
+            {
+                continue;
+            }
+            let previous = previous.and_then(|(value, _)| value);
+            match fetch(source) {
+                Ok(value) => {
+                    store(state, &svc.id, &source.key, Some(value.clone()));
+                    if previous.as_ref() != Some(&value) {
+                        logging::append_log(
+                            app,
+                            state,
+                            LogLevel::Info,
+                            format!(
+                                "TXT '{}' of service '{}' changed to '{}'",
+                                source.key, svc.name, value
+                            ),
+                            Some(svc.id.clone()),
+                        );
+                        updated = true;
+                    }
+                }
+                Err(e) => {
+                    // Keep the last good value and retry after the interval
+                    store(state, &svc.id, &source.key, previous);
+                    logging::append_log(
+                        app,
+                        state,
+                        LogLevel::Warn,
+                        format!(
+                            "Failed to refresh TXT '{}' of service '{}', keeping the last value: {}",
+                            source.key, svc.name, e
+                        ),
+                        Some(svc.id.clone()),
+                    );
+                }
+            }
+        }
+        if updated && *status == ServiceStatus::Running {
+            commands::republish_service(app, state, svc, svc, &hostname);
+            changed = true;
+        }
+    }
+
+    if changed {
+        if let Ok(views) = commands::build_views(state) {
+            let _ = app.emit("services-changed", &views);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn entry(key: &str, value: &str) -> TxtEntry {
+        TxtEntry {
+            key: key.into(),
+            value: Some(value.into()),
+            binary: false,
+        }
+    }
+
+    fn source(key: &str, kind: TxtSourceKind) -> TxtSource {
+        TxtSource {
+            key: key.into(),
+            kind,
+            interval_secs: 60,
+        }
+    }
+
+    #[test]
+    fn merge_replaces_same_key_and_appends_others() {
+        let txt = vec![entry("path", "/"), entry("Version", "dev")];
+        let merged = merge(
+            &txt,
+            &[
+                ("version".into(), "1.4.0".into()),
+                ("commit".into(), "abc123".into()),
+            ],
+        );
+        assert_eq!(
+            merged,
+            vec![
+                entry("path", "/"),
+                entry("version", "1.4.0"),
+                entry("commit", "abc123"),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_rejects_bad_sources() {
+        let command = || TxtSourceKind::Command {
+            command: "git rev-parse HEAD".into(),
+        };
+        assert!(validate(&[source("commit", command())]).is_ok());
+        assert!(validate(&[source("a=b", command())]).is_err());
+        assert!(validate(&[source("commit", command()), source("Commit", command())]).is_err());
+        assert!(validate(&[TxtSource {
+            interval_secs: 0,
+            ..source("commit", command())
+        }])
+        .is_err());
+        assert!(validate(&[source("path", TxtSourceKind::File { path: " ".into() })]).is_err());
+    }
+
+    #[test]
+    fn fetch_reads_trimmed_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "  42  ").unwrap();
+        let depth = source(
+            "depth",
+            TxtSourceKind::File {
+                path: file.path().display().to_string(),
+            },
+        );
+        assert_eq!(fetch(&depth).unwrap(), "42");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn fetch_runs_command() {
+        let ok = source(
+            "build",
+            TxtSourceKind::Command {
+                command: "echo v1.2.3".into(),
+            },
+        );
+        assert_eq!(fetch(&ok).unwrap(), "v1.2.3");
+
+        let failing = source(
+            "build",
+            TxtSourceKind::Command {
+                command: "exit 3".into(),
+            },
+        );
+        assert!(matches!(fetch(&failing), Err(AppError::Txt(_))));
+    }
+}
@@ -0,0 +1,342 @@
+use crate::commands;
+use crate::error::AppError;
+use crate::logging;
+use crate::mdns;
+use crate::models::{LogLevel, ServiceConfig, ServiceStatus, Verification};
+use crate::network;
+use crate::state::AppState;
+use crate::txt;
+use mdns_sd::{Receiver, ResolvedService, ServiceDaemon, ServiceEvent};
+use std::collections::{BTreeSet, HashMap};
+use std::net::IpAddr;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How long to browse for our own instance before giving up. Probing and
+/// the first announcement take roughly a second.
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often a shared browse checks whether anyone still waits on it.
+const BROWSE_POLL: Duration = Duration::from_millis(250);
+
+/// What browsing returned for our instance.
+#[derive(Debug, Clone, Default)]
+pub struct Observed {
+    pub host: String,
+    pub port: u16,
+    pub addresses: Vec<IpAddr>,
+    pub txt: Vec<(String, Option<Vec<u8>>)>,
+}
+
+impl Observed {
+    fn from_resolved(resolved: &ResolvedService) -> Self {
+        Observed {
+            host: resolved.host.clone(),
+            port: resolved.port,
+            addresses: resolved.addresses.iter().map(|a| a.to_ip_addr()).collect(),
+            txt: resolved
+                .txt_properties
+                .iter()
+                .map(|p| (p.key().to_string(), p.val().map(<[u8]>::to_vec)))
+                .collect(),
+        }
+    }
+}
+
+/// Verifications waiting on a browse, keyed by fully qualified service type.
+/// mdns-sd runs one browse per type on a daemon, where a second one
+/// replaces the first and stopping either ends both, so verifications of
+/// the same type share one browse and get their results handed out by
+/// fullname.
+#[derive(Default)]
+pub struct Verifier {
+    browses: HashMap<String, Vec<Waiter>>,
+    next_token: u64,
+}
+
+struct Waiter {
+    token: u64,
+    service_id: String,
+    fullname: String,
+    sender: mpsc::Sender<Observed>,
+}
+
+/// Browse for a just-published service and record whether the resolved records match the config.
+pub fn spawn_verification(app: &AppHandle, svc: &ServiceConfig, hostname: &str) {
+    let state = app.state::<AppState>();
+    {
+        let mut verifications = state.verifications.lock().unwrap();
+        verifications.insert(svc.id.clone(), Verification::Pending);
+    }
+
+    let published = commands::published_config(&state, svc);
+    let app = app.clone();
+    let hostname = hostname.to_string();
+    thread::spawn(move || {
+        let state = app.state::<AppState>();
+        let fullname = mdns::service_fullname(&published);
+        let (key, token, receiver) = match subscribe(&app, &published) {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                let reason = format!("could not browse for '{}': {}", fullname, e);
+                record(&app, &published, Verification::Unverified { reason });
+                return;
+            }
+        };
+        let result = match receiver.recv_timeout(VERIFY_TIMEOUT) {
+            Ok(observed) => match compare(&published, &hostname, &observed) {
+                Ok(()) => Verification::Verified,
+                Err(reason) => Verification::Unverified { reason },
+            },
+            // A newer verification of the service took over
+            Err(RecvTimeoutError::Disconnected) => return,
+            Err(RecvTimeoutError::Timeout) => {
+                let mut verifier = state.verifier.lock().unwrap();
+                if let Some(waiters) = verifier.browses.get_mut(&key) {
+                    waiters.retain(|w| w.token != token);
+                }
+                Verification::Unverified {
+                    reason: format!(
+                        "no answer for '{}' within {}s",
+                        fullname,
+                        VERIFY_TIMEOUT.as_secs()
+                    ),
+                }
+            }
+        };
+        record(&app, &published, result);
+    });
+}
+
+/// Wait for `svc`'s fullname on the browse for its type, starting one if
+/// none runs yet. An earlier wait for the same service is dropped.
+fn subscribe(
+    app: &AppHandle,
+    svc: &ServiceConfig,
+) -> Result<(String, u64, mpsc::Receiver<Observed>), AppError> {
+    let state = app.state::<AppState>();
+    let daemon = state.daemon.lock().unwrap().clone();
+    let key = mdns::to_mdns_type(&svc.service_type);
+    let (sender, receiver) = mpsc::channel();
+
+    // Browses start and stop under the lock so the daemon sees them in order
+    let mut verifier = state.verifier.lock().unwrap();
+    if !verifier.browses.contains_key(&key) {
+        let events = daemon
+            .browse(&key)
+            .map_err(|e| AppError::Mdns(e.to_string()))?;
+        verifier.browses.insert(key.clone(), Vec::new());
+        let app = app.clone();
+        let key = key.clone();
+        thread::spawn(move || run_browse(&app, &daemon, &key, &events));
+    }
+    verifier.next_token += 1;
+    let token = verifier.next_token;
+    let waiters = verifier.browses.get_mut(&key).unwrap();
+    waiters.retain(|w| w.service_id != svc.id);
+    waiters.push(Waiter {
+        token,
+        service_id: svc.id.clone(),
+        fullname: mdns::service_fullname(svc),
+        sender,
+    });
+    Ok((key, token, receiver))
+}
+
+/// Hand resolved instances to the verifications waiting for them, and stop
+/// browsing once none is left.
+fn run_browse(app: &AppHandle, daemon: &ServiceDaemon, key: &str, events: &Receiver<ServiceEvent>) {
+    let state = app.state::<AppState>();
+    loop {
+        let event = events.recv_timeout(BROWSE_POLL);
+        let mut verifier = state.verifier.lock().unwrap();
+        let Some(waiters) = verifier.browses.get_mut(key) else {
+            return;
+        };
+        if let Ok(ServiceEvent::ServiceResolved(resolved)) = event {
+            waiters.retain(|w| {
+                if !w.fullname.eq_ignore_ascii_case(&resolved.fullname) {
+                    return true;
+                }
+                let _ = w.sender.send(Observed::from_resolved(&resolved));
+                false
+            });
+        }
+        if waiters.is_empty() || events.is_disconnected() {
+            let _ = daemon.stop_browse(key);
+            verifier.browses.remove(key);
+            return;
+        }
+    }
+}
+
+/// Compare what was resolved with what `svc` should publish. The error is a
+/// short reason for the first mismatch found.
+pub fn compare(svc: &ServiceConfig, hostname: &str, observed: &Observed) -> Result<(), String> {
+    if observed.port != svc.port {
+        return Err(format!(
+            "SRV port is {} instead of {}",
+            observed.port, svc.port
+        ));
+    }
+
+    let host = mdns::service_host(svc, hostname).map_err(|e| e.to_string())?;
+    if !observed.host.eq_ignore_ascii_case(&host) {
+        return Err(format!(
+            "SRV target is '{}' instead of '{}'",
+            observed.host, host
+        ));
+    }
+
+    let expected: BTreeSet<(String, Option<Vec<u8>>)> = txt::to_properties(&svc.txt)
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|p| (p.key().to_ascii_lowercase(), p.val().map(<[u8]>::to_vec)))
+        .collect();
+    let actual: BTreeSet<(String, Option<Vec<u8>>)> = observed
+        .txt
+        .iter()
+        .map(|(k, v)| (k.to_ascii_lowercase(), v.clone()))
+        .collect();
+    if let Some((key, _)) = expected.symmetric_difference(&actual).next() {
+        return Err(format!("TXT entry '{}' differs", key));
+    }
+
+    if observed.addresses.is_empty() {
+        return Err("no addresses resolved".into());
+    }
+    match &svc.proxy {
+        Some(proxy) => {
+            if let Some(missing) = proxy
+                .addresses
+                .iter()
+                .find(|a| !observed.addresses.contains(a))
+            {
+                return Err(format!("address {} was not resolved", missing));
+            }
+        }
+        None => {
+            let local: Vec<IpAddr> = network::get_interfaces()
+                .iter()
+                .flat_map(|i| i.addresses.iter().filter_map(|a| a.parse().ok()))
+                .collect();
+            if !observed.addresses.iter().any(|a| local.contains(a)) {
+                return Err("none of the resolved addresses belong to this host".into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Store the result unless the service was stopped or republished meanwhile.
+fn record(app: &AppHandle, published: &ServiceConfig, result: Verification) {
+    let state = app.state::<AppState>();
+    {
+        let statuses = state.statuses.lock().unwrap();
+        if statuses.get(&published.id).copied() != Some(ServiceStatus::Running) {
+            return;
+        }
+    }
+    let current = commands::published_config(&state, published);
+    if mdns::service_fullname(&current) != mdns::service_fullname(published) {
+        return;
+    }
+    {
+        let mut verifications = state.verifications.lock().unwrap();
+        if verifications.get(&published.id) != Some(&Verification::Pending) {
+            return;
+        }
+        verifications.insert(published.id.clone(), result.clone());
+    }
+
+    if let Verification::Unverified { reason } = &result {
+        logging::append_log(
+            app,
+            &state,
+            LogLevel::Warn,
+            format!(
+                "Service '{}' could not be verified: {}",
+                published.name, reason
+            ),
+            Some(published.id.clone()),
+        );
+    }
+    if let Ok(views) = commands::build_views(&state) {
+        let _ = app.emit("services-changed", &views);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProxyTarget, TxtEntry};
+
+    fn proxied_config() -> ServiceConfig {
+        ServiceConfig {
+            id: "test-id".into(),
+            name: "Printer".into(),
+            service_type: "_ipp._tcp".into(),
+            port: 631,
+            txt: vec![TxtEntry::text("rp", "ipp/print")],
+            enabled: true,
+            proxy: Some(ProxyTarget {
+                host: "printer".into(),
+                addresses: vec!["192.168.1.50".parse().unwrap()],
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn matching_observation() -> Observed {
+        Observed {
+            host: "printer.local.".into(),
+            port: 631,
+            addresses: vec!["192.168.1.50".parse().unwrap()],
+            txt: vec![("rp".into(), Some(b"ipp/print".to_vec()))],
+        }
+    }
+
+    #[test]
+    fn compare_accepts_matching_records() {
+        assert!(compare(&proxied_config(), "myhost", &matching_observation()).is_ok());
+    }
+
+    #[test]
+    fn compare_reports_port_and_host_mismatch() {
+        let observed = Observed {
+            port: 80,
+            ..matching_observation()
+        };
+        let err = compare(&proxied_config(), "myhost", &observed).unwrap_err();
+        assert!(err.contains("port"));
+
+        let observed = Observed {
+            host: "other.local.".into(),
+            ..matching_observation()
+        };
+        let err = compare(&proxied_config(), "myhost", &observed).unwrap_err();
+        assert!(err.contains("SRV target"));
+    }
+
+    #[test]
+    fn compare_reports_txt_mismatch() {
+        let observed = Observed {
+            txt: vec![("rp".into(), Some(b"other".to_vec()))],
+            ..matching_observation()
+        };
+        let err = compare(&proxied_config(), "myhost", &observed).unwrap_err();
+        assert!(err.contains("'rp'"));
+    }
+
+    #[test]
+    fn compare_reports_missing_proxy_address() {
+        let observed = Observed {
+            addresses: vec!["192.168.1.51".parse().unwrap()],
+            ..matching_observation()
+        };
+        let err = compare(&proxied_config(), "myhost", &observed).unwrap_err();
+        assert!(err.contains("192.168.1.50"));
+    }
+}
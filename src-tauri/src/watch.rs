@@ -0,0 +1,281 @@
+use crate::browse;
+use crate::error::AppError;
+use crate::logging;
+use crate::mdns;
+use crate::models::{DiscoveredService, LogLevel, WatchChange, WatchEntry, WatchEvent};
+use crate::state::AppState;
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+
+/// How long after a type is first watched its results count as the initial
+/// snapshot rather than as services appearing.
+const SNAPSHOT_WINDOW: Duration = Duration::from_secs(5);
+
+/// Fully qualified type to browse for a watch target: the type of a watched
+/// instance, or the watched type itself.
+pub fn target_type(target: &str) -> Result<String, AppError> {
+    match mdns::split_fullname(target.trim()) {
+        Some((_, service_type)) => Ok(mdns::to_mdns_type(&service_type)),
+        None => browse::validate_type(target),
+    }
+}
+
+fn matches(entry: &WatchEntry, fullname: &str, ty_domain: &str) -> bool {
+    let target = entry.target.trim();
+    match mdns::split_fullname(target) {
+        Some(_) => target
+            .trim_end_matches('.')
+            .eq_ignore_ascii_case(fullname.trim_end_matches('.')),
+        None => mdns::to_browse_type(target).is_ok_and(|t| t.eq_ignore_ascii_case(ty_domain)),
+    }
+}
+
+/// Short description of what changed between two resolutions of the same
+/// instance, or `None` if nothing we alert on did.
+pub fn describe_change(
+    previous: &DiscoveredService,
+    current: &DiscoveredService,
+) -> Option<String> {
+    let mut changes = Vec::new();
+    if previous.port != current.port {
+        changes.push(format!("port {} -> {}", previous.port, current.port));
+    }
+    if !previous.host.eq_ignore_ascii_case(&current.host) {
+        changes.push(format!("host {} -> {}", previous.host, current.host));
+    }
+    if previous.txt != current.txt {
+        changes.push("TXT record changed".to_string());
+    }
+    (!changes.is_empty()).then(|| changes.join(", "))
+}
+
+/// Bring the browse queries in line with the watchlist: query every watched
+/// type and stop queries the watchlist no longer needs.
+pub fn sync(app: &AppHandle, state: &AppState) {
+    let (entries, needed): (Vec<WatchEntry>, HashSet<String>) = {
+        let config = state.config.lock().unwrap();
+        let needed = config
+            .watchlist
+            .iter()
+            .filter_map(|w| target_type(&w.target).ok())
+            .collect();
+        (config.watchlist.clone(), needed)
+    };
+
+    let stale: Vec<String> = {
+        let mut browser = state.browser.lock().unwrap();
+        let previous = std::mem::replace(&mut browser.watched, needed.clone());
+        let snapshot_end = Instant::now() + SNAPSHOT_WINDOW;
+        browser.watch_priming.retain(|ty, _| needed.contains(ty));
+        for ty in needed.difference(&previous) {
+            browser.watch_priming.insert(ty.clone(), snapshot_end);
+        }
+        browser.watch_seen.retain(|_, seen| {
+            entries
+                .iter()
+                .any(|w| matches(w, &seen.fullname, &seen.service_type))
+        });
+        previous
+            .difference(&needed)
+            .filter(|ty| browser.queries.contains(*ty) && !browser.in_use(ty))
+            .cloned()
+            .collect()
+    };
+
+    for ty in &needed {
+        if let Err(e) = browse::query(app, state, ty) {
+            logging::append_log(
+                app,
+                state,
+                LogLevel::Warn,
+                format!("Failed to watch {}: {}", ty, e),
+                None,
+            );
+        }
+    }
+    if !stale.is_empty() {
+        if let Ok(daemon) = browse::browse_daemon(state) {
+            for ty in &stale {
+                let _ = daemon.stop_browse(ty);
+            }
+        }
+    }
+}
+
+/// Whether results for `ty` still belong to the first snapshot of its query.
+fn in_snapshot(priming: &HashMap<String, Instant>, ty: &str, now: Instant) -> bool {
+    priming
+        .iter()
+        .any(|(watched, end)| watched.eq_ignore_ascii_case(ty) && now < *end)
+}
+
+fn watch_id_for(state: &AppState, fullname: &str, ty_domain: &str) -> Option<String> {
+    let config = state.config.lock().unwrap();
+    config
+        .watchlist
+        .iter()
+        .find(|w| matches(w, fullname, ty_domain))
+        .map(|w| w.id.clone())
+}
+
+/// A browse query resolved `service`: report it if it is watched and new or
+/// changed since it was last seen.
+pub fn observe_resolved(app: &AppHandle, state: &AppState, service: &DiscoveredService) {
+    let Some(watch_id) = watch_id_for(state, &service.fullname, &service.service_type) else {
+        return;
+    };
+    let (previous, priming) = {
+        let mut browser = state.browser.lock().unwrap();
+        let priming = in_snapshot(
+            &browser.watch_priming,
+            &service.service_type,
+            Instant::now(),
+        );
+        let previous = browser
+            .watch_seen
+            .insert(service.fullname.to_ascii_lowercase(), service.clone());
+        (previous, priming)
+    };
+    let change = match previous {
+        // Already there when the watch started, so nothing appeared
+        None if priming => return,
+        None => WatchChange::Appeared,
+        Some(previous) => match describe_change(&previous, service) {
+            Some(details) => WatchChange::Changed { details },
+            None => return,
+        },
+    };
+    report(app, state, watch_id, &service.fullname, change);
+}
+
+/// A browse query saw `fullname` go away: report it if it was watched.
+pub fn observe_removed(app: &AppHandle, state: &AppState, fullname: &str) {
+    let seen = {
+        let mut browser = state.browser.lock().unwrap();
+        browser.watch_seen.remove(&fullname.to_ascii_lowercase())
+    };
+    let Some(seen) = seen else {
+        return;
+    };
+    let Some(watch_id) = watch_id_for(state, fullname, &seen.service_type) else {
+        return;
+    };
+    report(app, state, watch_id, fullname, WatchChange::Disappeared);
+}
+
+fn report(
+    app: &AppHandle,
+    state: &AppState,
+    watch_id: String,
+    fullname: &str,
+    change: WatchChange,
+) {
+    let (level, message) = match &change {
+        WatchChange::Appeared => (
+            LogLevel::Info,
+            format!("Watched service '{}' appeared", fullname),
+        ),
+        WatchChange::Disappeared => (
+            LogLevel::Warn,
+            format!("Watched service '{}' disappeared", fullname),
+        ),
+        WatchChange::Changed { details } => (
+            LogLevel::Info,
+            format!("Watched service '{}' changed: {}", fullname, details),
+        ),
+    };
+
+    logging::append_log(app, state, level, message.clone(), None);
+    let event = WatchEvent {
+        timestamp: Utc::now(),
+        watch_id,
+        fullname: fullname.to_string(),
+        change,
+    };
+    let _ = app.emit("watch-event", &event);
+
+    let _ = app
+        .notification()
+        .builder()
+        .title("Noroshi")
+        .body(&message)
+        .show();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TxtEntry;
+
+    fn discovered(port: u16, txt: Vec<TxtEntry>) -> DiscoveredService {
+        DiscoveredService {
+            fullname: "Cache._http._tcp.local.".into(),
+            name: "Cache".into(),
+            service_type: "_http._tcp.local.".into(),
+            host: "builder.local.".into(),
+            port,
+            addresses: vec!["192.168.1.20".parse().unwrap()],
+            txt,
+            own: false,
+        }
+    }
+
+    fn entry(target: &str) -> WatchEntry {
+        WatchEntry {
+            id: "w1".into(),
+            target: target.into(),
+        }
+    }
+
+    #[test]
+    fn target_type_accepts_fullnames_and_types() {
+        assert_eq!(
+            target_type("Cache._http._tcp.local.").unwrap(),
+            "_http._tcp.local."
+        );
+        assert_eq!(target_type("_ipp._tcp").unwrap(), "_ipp._tcp.local.");
+        assert!(target_type("printer").is_err());
+    }
+
+    #[test]
+    fn matches_instance_or_type() {
+        let fullname = "Cache._http._tcp.local.";
+        let ty = "_http._tcp.local.";
+        assert!(matches(&entry("cache._http._tcp.local."), fullname, ty));
+        assert!(matches(&entry("_http._tcp"), fullname, ty));
+        assert!(!matches(&entry("Other._http._tcp.local."), fullname, ty));
+        assert!(!matches(&entry("_ipp._tcp"), fullname, ty));
+        // A subtype watch only matches what its subtype query resolved
+        let sub = "_printer._sub._http._tcp.local.";
+        assert!(matches(&entry("_printer._sub._http._tcp"), fullname, sub));
+        assert!(!matches(&entry("_printer._sub._http._tcp"), fullname, ty));
+    }
+
+    #[test]
+    fn in_snapshot_until_window_ends() {
+        let now = Instant::now();
+        let priming = HashMap::from([("_http._tcp.local.".to_string(), now + SNAPSHOT_WINDOW)]);
+        assert!(in_snapshot(&priming, "_HTTP._tcp.local.", now));
+        assert!(!in_snapshot(&priming, "_ipp._tcp.local.", now));
+        assert!(!in_snapshot(
+            &priming,
+            "_http._tcp.local.",
+            now + SNAPSHOT_WINDOW
+        ));
+    }
+
+    #[test]
+    fn describe_change_reports_port_and_txt() {
+        let before = discovered(80, vec![TxtEntry::text("v", "1")]);
+        assert_eq!(describe_change(&before, &before.clone()), None);
+
+        let after = discovered(8080, vec![TxtEntry::text("v", "2")]);
+        assert_eq!(
+            describe_change(&before, &after).as_deref(),
+            Some("port 80 -> 8080, TXT record changed")
+        );
+    }
+}
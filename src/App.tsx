@@ -3,10 +3,11 @@ import { Layout, type Tab } from "./components/Layout";
 import { ServiceTable } from "./components/ServiceTable";
 import { ServiceFormDialog } from "./components/ServiceFormDialog";
 import { BulkActions } from "./components/BulkActions";
+import { BrowseView } from "./components/BrowseView";
 import { MonitoringView } from "./components/MonitoringView";
 import { SettingsView } from "./components/SettingsView";
 import { useServices } from "./hooks/useServices";
-import type { ServiceView } from "./types";
+import type { ServiceView, TxtEntry } from "./types";
 
 function App() {
   const {
@@ -17,6 +18,7 @@ function App() {
     updateService,
     deleteService,
     toggleService,
+    startServiceFor,
     startAll,
     stopAll,
     importConfig,
@@ -42,7 +44,7 @@ function App() {
     name: string,
     serviceType: string,
     port: number,
-    txt: Record<string, string>,
+    txt: TxtEntry[],
     enabled: boolean,
   ) => {
     if (editingService) {
@@ -96,6 +98,7 @@ function App() {
             <ServiceTable
               services={services}
               onToggle={toggleService}
+              onStartFor={startServiceFor}
               onEdit={handleEdit}
               onDelete={handleDelete}
             />
@@ -111,6 +114,8 @@ function App() {
         </>
       )}
 
+      {activeTab === "browse" && <BrowseView />}
+
       {activeTab === "monitor" && <MonitoringView services={services} />}
 
       {activeTab === "settings" && <SettingsView onImport={importConfig} />}
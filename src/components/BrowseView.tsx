@@ -0,0 +1,161 @@
+import { useState } from "react";
+import { useBrowse } from "../hooks/useBrowse";
+import type { TxtEntry } from "../types";
+
+function formatTxtEntry(entry: TxtEntry): string {
+  if (entry.value === undefined) return entry.key;
+  if (entry.binary) return `${entry.key}=<base64:${entry.value}>`;
+  return `${entry.key}=${entry.value}`;
+}
+
+export function BrowseView() {
+  const { browsing, results, types, error, start, stop, clone } = useBrowse();
+  const [serviceType, setServiceType] = useState("_http._tcp");
+
+  const sorted = [...results].sort((a, b) => a.name.localeCompare(b.name));
+
+  return (
+    <div className="space-y-4">
+      {error && (
+        <div className="rounded-lg border border-red-200 bg-red-50 p-3 text-sm text-red-700">
+          {error}
+        </div>
+      )}
+
+      <div className="flex items-center gap-2">
+        <input
+          type="text"
+          value={serviceType}
+          onChange={(e) => setServiceType(e.target.value)}
+          placeholder="_http._tcp"
+          className="flex-1 rounded border border-gray-300 px-3 py-1.5 font-mono text-sm"
+        />
+        <button
+          onClick={() => start(serviceType)}
+          disabled={!serviceType.trim()}
+          className="rounded bg-gray-900 px-3 py-1.5 text-sm font-medium text-white hover:bg-gray-700 disabled:opacity-50"
+        >
+          Browse
+        </button>
+        {browsing && (
+          <button
+            onClick={stop}
+            className="rounded bg-gray-200 px-3 py-1.5 text-sm hover:bg-gray-300"
+          >
+            Stop
+          </button>
+        )}
+      </div>
+
+      <div>
+        <h3 className="mb-2 text-sm font-semibold text-gray-700">
+          Service Types on the Network
+        </h3>
+        {types.length === 0 ? (
+          <div className="text-xs text-gray-400">No service types seen yet</div>
+        ) : (
+          <div className="flex flex-wrap gap-1">
+            {types.map((t) => (
+              <button
+                key={t.type}
+                onClick={() => {
+                  setServiceType(t.type);
+                  start(t.type);
+                }}
+                title={`First seen ${new Date(t.first_seen).toLocaleTimeString()}, last seen ${new Date(t.last_seen).toLocaleTimeString()}`}
+                className={`rounded-full px-2 py-0.5 font-mono text-xs ${
+                  t.type === browsing
+                    ? "bg-gray-900 text-white"
+                    : "bg-gray-100 text-gray-700 hover:bg-gray-200"
+                }`}
+              >
+                {t.type} ({t.instances})
+              </button>
+            ))}
+          </div>
+        )}
+      </div>
+
+      {browsing && (
+        <div className="text-xs text-gray-500">
+          Browsing <span className="font-mono">{browsing}</span>
+        </div>
+      )}
+
+      {sorted.length === 0 ? (
+        <div className="rounded-lg border border-gray-200 bg-white p-8 text-center text-sm text-gray-400">
+          {browsing ? "No services found yet" : "Enter a service type to browse"}
+        </div>
+      ) : (
+        <table className="w-full rounded-lg border border-gray-200 bg-white">
+          <thead>
+            <tr className="border-b border-gray-200 bg-gray-50 text-left text-xs font-medium uppercase text-gray-500">
+              <th className="px-4 py-2">Name</th>
+              <th className="px-4 py-2">Host</th>
+              <th className="px-4 py-2">Port</th>
+              <th className="px-4 py-2">Addresses</th>
+              <th className="px-4 py-2">TXT</th>
+              <th className="px-4 py-2"></th>
+            </tr>
+          </thead>
+          <tbody>
+            {sorted.map((service) => (
+              <tr
+                key={service.fullname}
+                className="border-b border-gray-200 text-sm"
+              >
+                <td className="px-4 py-2 font-medium">
+                  {service.name}
+                  {service.own && (
+                    <span className="ml-2 rounded-full bg-blue-100 px-2 py-0.5 text-xs text-blue-800">
+                      this app
+                    </span>
+                  )}
+                </td>
+                <td className="px-4 py-2 font-mono text-gray-600">
+                  {service.host}
+                </td>
+                <td className="px-4 py-2 text-gray-600">{service.port}</td>
+                <td className="px-4 py-2 font-mono text-xs text-gray-600">
+                  {service.addresses.join(", ")}
+                </td>
+                <td className="px-4 py-2">
+                  <div className="flex flex-wrap gap-1">
+                    {service.txt.map((entry) => (
+                      <span
+                        key={entry.key}
+                        className="inline-block rounded bg-gray-100 px-1.5 py-0.5 font-mono text-xs"
+                      >
+                        {formatTxtEntry(entry)}
+                      </span>
+                    ))}
+                  </div>
+                </td>
+                <td className="px-4 py-2">
+                  {!service.own && (
+                    <div className="flex gap-2 text-xs">
+                      <button
+                        onClick={() => clone(service.fullname, false)}
+                        title="Add a disabled copy of this service to your services"
+                        className="text-blue-600 hover:text-blue-800"
+                      >
+                        Clone
+                      </button>
+                      <button
+                        onClick={() => clone(service.fullname, true)}
+                        title="Re-advertise this service on behalf of its host"
+                        className="text-blue-600 hover:text-blue-800"
+                      >
+                        Proxy
+                      </button>
+                    </div>
+                  )}
+                </td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+    </div>
+  );
+}
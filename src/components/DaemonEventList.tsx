@@ -0,0 +1,54 @@
+import type { DaemonEventEntry } from "../types";
+
+interface Props {
+  events: DaemonEventEntry[];
+}
+
+function describe(entry: DaemonEventEntry): string {
+  switch (entry.kind) {
+    case "error":
+      return `Error: ${entry.message}`;
+    case "ip_added":
+      return `Address added: ${entry.address}`;
+    case "ip_removed":
+      return `Address removed: ${entry.address}`;
+    case "name_changed":
+      return `Renamed ${entry.original} → ${entry.new_name}`;
+    case "announced":
+      return `Announced ${entry.fullname} on ${entry.interface}`;
+  }
+}
+
+const kindColors: Record<DaemonEventEntry["kind"], string> = {
+  error: "text-red-600",
+  ip_added: "text-gray-600",
+  ip_removed: "text-yellow-700",
+  name_changed: "text-orange-700",
+  announced: "text-gray-400",
+};
+
+export function DaemonEventList({ events }: Props) {
+  return (
+    <div>
+      <h3 className="mb-3 text-sm font-semibold text-gray-700">
+        Network Events
+      </h3>
+      {events.length === 0 ? (
+        <div className="rounded-lg border border-gray-200 bg-white p-4 text-center text-sm text-gray-400">
+          No network events yet
+        </div>
+      ) : (
+        <ul className="max-h-48 space-y-1 overflow-y-auto rounded-lg border border-gray-200 bg-white p-3 font-mono text-xs">
+          {[...events].reverse().map((entry, i) => (
+            <li key={`${entry.timestamp}-${i}`} className={kindColors[entry.kind]}>
+              <span className="mr-2 text-gray-400">
+                {new Date(entry.timestamp).toLocaleTimeString()}
+              </span>
+              {describe(entry)}
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+}
@@ -1,7 +1,9 @@
 import { useEffect, useState } from "react";
-import { getHostName } from "../lib/commands";
+import { listen } from "@tauri-apps/api/event";
+import { getHostInfo } from "../lib/commands";
+import type { HostInfo } from "../types";
 
-export type Tab = "services" | "monitor" | "settings";
+export type Tab = "services" | "browse" | "monitor" | "settings";
 
 interface Props {
   activeTab: Tab;
@@ -10,10 +12,20 @@ interface Props {
 }
 
 export function Layout({ activeTab, onTabChange, children }: Props) {
-  const [hostname, setHostname] = useState("");
+  const [hostInfo, setHostInfo] = useState<HostInfo | null>(null);
 
   useEffect(() => {
-    getHostName().then(setHostname).catch(console.error);
+    getHostInfo().then(setHostInfo).catch(console.error);
+  }, []);
+
+  // A hostname override or an import re-registers services
+  useEffect(() => {
+    const unlisten = listen("services-changed", () => {
+      getHostInfo().then(setHostInfo).catch(console.error);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
   }, []);
 
   return (
@@ -21,10 +33,16 @@ export function Layout({ activeTab, onTabChange, children }: Props) {
       <header className="border-b border-gray-200 bg-white px-6 py-4">
         <div className="flex items-center justify-between">
           <h1 className="text-xl font-bold text-gray-900">noroshi</h1>
-          {hostname && (
+          {hostInfo && (
             <span className="group relative text-sm text-gray-500">
-              Host: <span className="font-mono">{hostname}</span>
+              Host: <span className="font-mono">{hostInfo.effective}</span>
               <div className="pointer-events-none absolute top-full right-0 z-10 mt-1 hidden w-72 rounded-lg border border-gray-200 bg-white p-3 text-xs text-gray-600 shadow-lg group-hover:block">
+                <dl className="mb-2 grid grid-cols-[auto_1fr] gap-x-2">
+                  <dt className="font-medium">mDNS name:</dt>
+                  <dd className="font-mono">{hostInfo.effective}.local</dd>
+                  <dt className="font-medium">OS hostname:</dt>
+                  <dd className="font-mono">{hostInfo.os}</dd>
+                </dl>
                 <p className="mb-2 font-semibold text-gray-800">
                   How to change hostname:
                 </p>
@@ -47,7 +65,8 @@ export function Layout({ activeTab, onTabChange, children }: Props) {
                   </li>
                 </ul>
                 <p className="mt-2 text-gray-400">
-                  Restart this app after changing hostname.
+                  Restart this app after changing hostname, or set an mDNS
+                  hostname override in Settings.
                 </p>
               </div>
             </span>
@@ -64,6 +83,16 @@ export function Layout({ activeTab, onTabChange, children }: Props) {
           >
             Services
           </button>
+          <button
+            onClick={() => onTabChange("browse")}
+            className={`rounded-md px-3 py-1.5 text-sm font-medium transition-colors ${
+              activeTab === "browse"
+                ? "bg-gray-900 text-white"
+                : "text-gray-600 hover:bg-gray-100 hover:text-gray-900"
+            }`}
+          >
+            Browse
+          </button>
           <button
             onClick={() => onTabChange("monitor")}
             className={`rounded-md px-3 py-1.5 text-sm font-medium transition-colors ${
@@ -1,6 +1,8 @@
 import { StatusDashboard } from "./StatusDashboard";
 import { LogViewer } from "./LogViewer";
 import { NetworkInfo } from "./NetworkInfo";
+import { DaemonEventList } from "./DaemonEventList";
+import { WatchlistPanel } from "./WatchlistPanel";
 import { useMonitoring } from "../hooks/useMonitoring";
 import type { ServiceView } from "../types";
 
@@ -12,6 +14,7 @@ export function MonitoringView({ services }: Props) {
   const {
     logs,
     interfaces,
+    daemonEvents,
     levelFilter,
     setLevelFilter,
     clearLogs,
@@ -27,6 +30,8 @@ export function MonitoringView({ services }: Props) {
         onLevelFilterChange={setLevelFilter}
         onClear={clearLogs}
       />
+      <WatchlistPanel />
+      <DaemonEventList events={daemonEvents} />
       <NetworkInfo interfaces={interfaces} onRefresh={refreshInterfaces} />
     </div>
   );
@@ -1,5 +1,5 @@
 import { useState } from "react";
-import type { ServiceView } from "../types";
+import type { ServiceView, TxtEntry } from "../types";
 import { TxtRecordEditor } from "./TxtRecordEditor";
 
 const PRESET_SERVICE_TYPES = [
@@ -23,7 +23,7 @@ interface Props {
     name: string,
     serviceType: string,
     port: number,
-    txt: Record<string, string>,
+    txt: TxtEntry[],
     enabled: boolean,
   ) => void;
   onCancel: () => void;
@@ -40,8 +40,8 @@ export function ServiceFormDialog({ service, onSave, onCancel }: Props) {
   const serviceType =
     selectedPreset === CUSTOM_OPTION ? customType : selectedPreset;
   const [port, setPort] = useState(service?.port ?? 8080);
-  const [txt, setTxt] = useState<Record<string, string>>(
-    service ? { ...service.txt } : {},
+  const [txt, setTxt] = useState<TxtEntry[]>(
+    service ? [...service.txt] : [],
   );
   const [enabled, setEnabled] = useState(service?.enabled ?? true);
 
@@ -1,10 +1,11 @@
 import { openUrl } from "@tauri-apps/plugin-opener";
-import type { ServiceView } from "../types";
+import type { ServiceView, TxtEntry } from "../types";
 
 interface Props {
   service: ServiceView;
   hostname: string;
   onToggle: (id: string) => void;
+  onStartFor: (id: string, minutes: number) => void;
   onEdit: (service: ServiceView) => void;
   onDelete: (id: string) => void;
 }
@@ -13,6 +14,11 @@ const statusColors: Record<ServiceView["status"], string> = {
   running: "bg-green-100 text-green-800",
   stopped: "bg-gray-100 text-gray-600",
   error: "bg-red-100 text-red-800",
+  conflict: "bg-orange-100 text-orange-800",
+  unhealthy: "bg-yellow-100 text-yellow-800",
+  waiting: "bg-blue-100 text-blue-800",
+  scheduled: "bg-purple-100 text-purple-800",
+  retrying: "bg-red-50 text-red-700",
 };
 
 const serviceTypeToScheme: Record<string, string> = {
@@ -34,15 +40,47 @@ function getServiceUrl(service: ServiceView, hostname: string): string | null {
   return `${scheme}://${hostname}.local:${service.port}`;
 }
 
+function formatTime(iso: string): string {
+  return new Date(iso).toLocaleString(undefined, {
+    weekday: "short",
+    hour: "2-digit",
+    minute: "2-digit",
+  });
+}
+
+function formatTxtEntry(entry: TxtEntry): string {
+  if (entry.value === undefined) return entry.key;
+  if (entry.binary) return `${entry.key}=<base64:${entry.value}>`;
+  return `${entry.key}=${entry.value}`;
+}
+
+/** Where a published TXT value came from: its source or its template. */
+function txtOrigin(service: ServiceView, key: string): string | undefined {
+  const same = (k: string) => k.toLowerCase() === key.toLowerCase();
+  const source = service.txt_sources?.find((s) => same(s.key));
+  if (source) {
+    const from = source.source === "command" ? source.command : source.path;
+    return `${from} (every ${source.interval_secs}s)`;
+  }
+  const entry = service.txt.find((e) => same(e.key));
+  return entry && formatTxtEntry(entry);
+}
+
 export function ServiceRow({
   service,
   hostname,
   onToggle,
+  onStartFor,
   onEdit,
   onDelete,
 }: Props) {
-  const txtEntries = Object.entries(service.txt);
   const url = getServiceUrl(service, hostname);
+  const active =
+    service.status === "running" ||
+    service.status === "unhealthy" ||
+    service.status === "waiting" ||
+    service.status === "scheduled" ||
+    service.status === "retrying";
 
   return (
     <tr className="border-b border-gray-200 hover:bg-gray-50">
@@ -58,20 +96,39 @@ export function ServiceRow({
         ) : (
           service.name
         )}
+        {service.rendered_name && (
+          <div className="text-xs font-normal text-gray-500">
+            as {service.rendered_name}
+          </div>
+        )}
+        {service.published_name && (
+          <div className="text-xs font-normal text-orange-700">
+            published as {service.published_name}
+          </div>
+        )}
+        {service.pid !== undefined && (
+          <div
+            className="text-xs font-normal text-gray-500"
+            title={service.process?.command}
+          >
+            pid {service.pid}
+          </div>
+        )}
       </td>
       <td className="px-4 py-3 text-sm font-mono text-gray-600">
         {service.type}
       </td>
       <td className="px-4 py-3 text-sm text-gray-600">{service.port}</td>
       <td className="px-4 py-3 text-sm">
-        {txtEntries.length > 0 ? (
+        {(service.rendered_txt ?? service.txt).length > 0 ? (
           <div className="flex flex-wrap gap-1">
-            {txtEntries.map(([k, v]) => (
+            {(service.rendered_txt ?? service.txt).map((entry) => (
               <span
-                key={k}
+                key={entry.key}
+                title={service.rendered_txt && txtOrigin(service, entry.key)}
                 className="inline-block rounded bg-gray-100 px-1.5 py-0.5 text-xs font-mono"
               >
-                {k}={v}
+                {formatTxtEntry(entry)}
               </span>
             ))}
           </div>
@@ -85,19 +142,58 @@ export function ServiceRow({
         >
           {service.status}
         </span>
+        {service.verification?.state === "verified" && (
+          <div className="text-xs text-green-700">verified</div>
+        )}
+        {service.verification?.state === "unverified" && (
+          <div
+            className="text-xs text-orange-700"
+            title={service.verification.reason}
+          >
+            unverified
+          </div>
+        )}
+        {service.stops_at && (
+          <div className="text-xs text-gray-500">
+            stops at {formatTime(service.stops_at)}
+          </div>
+        )}
+        {service.next_transition && (
+          <div className="text-xs text-gray-500">
+            {service.status === "scheduled" ? "opens" : "closes"} at{" "}
+            {formatTime(service.next_transition)}
+          </div>
+        )}
+        {service.next_retry && (
+          <div className="text-xs text-gray-500">
+            attempt {service.retry_attempt}
+            {service.retry?.max_attempts !== undefined &&
+              ` of ${service.retry.max_attempts}`}
+            , next at {formatTime(service.next_retry)}
+          </div>
+        )}
       </td>
       <td className="px-4 py-3">
         <div className="flex items-center gap-2">
           <button
             onClick={() => onToggle(service.id)}
             className={`rounded px-3 py-1 text-xs font-medium ${
-              service.status === "running"
+              active
                 ? "bg-yellow-100 text-yellow-800 hover:bg-yellow-200"
                 : "bg-green-100 text-green-800 hover:bg-green-200"
             }`}
           >
-            {service.status === "running" ? "Stop" : "Start"}
+            {active ? "Stop" : "Start"}
           </button>
+          {!active && (
+            <button
+              onClick={() => onStartFor(service.id, 30)}
+              title="Start now and stop again after 30 minutes"
+              className="rounded bg-green-50 px-3 py-1 text-xs font-medium text-green-800 hover:bg-green-100"
+            >
+              30 min
+            </button>
+          )}
           <button
             onClick={() => onEdit(service)}
             className="rounded bg-gray-100 px-3 py-1 text-xs font-medium text-gray-700 hover:bg-gray-200"
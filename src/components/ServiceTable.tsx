@@ -6,16 +6,24 @@ import { ServiceRow } from "./ServiceRow";
 interface Props {
   services: ServiceView[];
   onToggle: (id: string) => void;
+  onStartFor: (id: string, minutes: number) => void;
   onEdit: (service: ServiceView) => void;
   onDelete: (id: string) => void;
 }
 
-export function ServiceTable({ services, onToggle, onEdit, onDelete }: Props) {
+export function ServiceTable({
+  services,
+  onToggle,
+  onStartFor,
+  onEdit,
+  onDelete,
+}: Props) {
   const [hostname, setHostname] = useState("");
 
+  // Refetched with the services, which are re-registered when it changes
   useEffect(() => {
     getHostName().then(setHostname).catch(console.error);
-  }, []);
+  }, [services]);
 
   if (services.length === 0) {
     return (
@@ -47,6 +55,7 @@ export function ServiceTable({ services, onToggle, onEdit, onDelete }: Props) {
               service={svc}
               hostname={hostname}
               onToggle={onToggle}
+              onStartFor={onStartFor}
               onEdit={onEdit}
               onDelete={onDelete}
             />
@@ -1,13 +1,16 @@
 import { useEffect, useRef, useState } from "react";
 import { exportConfig } from "../lib/commands";
-import { getHostName } from "../lib/commands";
+import { getHostInfo, setHostnameOverride } from "../lib/commands";
+import type { HostInfo } from "../types";
 
 interface Props {
   onImport: (json: string) => Promise<void>;
 }
 
 export function SettingsView({ onImport }: Props) {
-  const [hostname, setHostname] = useState("");
+  const [hostInfo, setHostInfo] = useState<HostInfo | null>(null);
+  const [override, setOverride] = useState("");
+  const [savingHostname, setSavingHostname] = useState(false);
   const [exporting, setExporting] = useState(false);
   const [importing, setImporting] = useState(false);
   const [message, setMessage] = useState<{
@@ -16,10 +19,34 @@ export function SettingsView({ onImport }: Props) {
   } | null>(null);
   const fileInputRef = useRef<HTMLInputElement>(null);
 
+  const showHostInfo = (info: HostInfo) => {
+    setHostInfo(info);
+    setOverride(info.hostname_override ?? "");
+  };
+
   useEffect(() => {
-    getHostName().then(setHostname).catch(console.error);
+    getHostInfo().then(showHostInfo).catch(console.error);
   }, []);
 
+  const handleHostnameSave = async (hostname: string | null) => {
+    setSavingHostname(true);
+    setMessage(null);
+    try {
+      const info = await setHostnameOverride(hostname);
+      showHostInfo(info);
+      setMessage({
+        type: "success",
+        text: info.hostname_override
+          ? `mDNS hostname set to ${info.effective}.local.`
+          : "mDNS hostname override cleared.",
+      });
+    } catch (e) {
+      setMessage({ type: "error", text: `Hostname change failed: ${e}` });
+    } finally {
+      setSavingHostname(false);
+    }
+  };
+
   const handleExport = async () => {
     setExporting(true);
     setMessage(null);
@@ -93,8 +120,59 @@ export function SettingsView({ onImport }: Props) {
       <section className="rounded-lg border border-gray-200 bg-white p-5">
         <h3 className="mb-3 text-sm font-semibold text-gray-700">Hostname</h3>
         <p className="mb-3 font-mono text-sm text-gray-900">
-          {hostname || "Loading..."}
+          {hostInfo ? `${hostInfo.effective}.local` : "Loading..."}
         </p>
+        <form
+          onSubmit={(e) => {
+            e.preventDefault();
+            handleHostnameSave(override.trim() || null);
+          }}
+          className="mb-3"
+        >
+          <label
+            htmlFor="hostname-override"
+            className="mb-1 block text-xs font-medium text-gray-600"
+          >
+            mDNS hostname override
+          </label>
+          <div className="flex gap-2">
+            <input
+              id="hostname-override"
+              type="text"
+              value={override}
+              onChange={(e) => setOverride(e.target.value)}
+              placeholder={hostInfo?.os ?? ""}
+              className="flex-1 rounded-md border border-gray-300 px-3 py-2 font-mono text-sm focus:border-gray-500 focus:outline-none"
+            />
+            <button
+              type="submit"
+              disabled={savingHostname}
+              className="rounded-md bg-gray-900 px-4 py-2 text-sm font-medium text-white transition-colors hover:bg-gray-800 disabled:opacity-50"
+            >
+              Save
+            </button>
+            {hostInfo?.hostname_override && (
+              <button
+                type="button"
+                onClick={() => handleHostnameSave(null)}
+                disabled={savingHostname}
+                className="rounded-md border border-gray-300 bg-white px-4 py-2 text-sm font-medium text-gray-700 transition-colors hover:bg-gray-50 disabled:opacity-50"
+              >
+                Clear
+              </button>
+            )}
+          </div>
+          <p className="mt-1 text-xs text-gray-500">
+            Advertise services under this name instead of the OS hostname
+            {hostInfo && (
+              <>
+                {" "}
+                (<span className="font-mono">{hostInfo.os}</span>)
+              </>
+            )}
+            . Running services are re-registered.
+          </p>
+        </form>
         <div className="rounded-md bg-gray-50 p-3 text-xs text-gray-600">
           <p className="mb-2 font-semibold text-gray-700">
             To change hostname, use your OS settings:
@@ -118,7 +196,8 @@ export function SettingsView({ onImport }: Props) {
             </li>
           </ul>
           <p className="mt-2 text-gray-400">
-            Restart this app after changing hostname.
+            Restart this app after changing hostname, unless an override is
+            set.
           </p>
         </div>
       </section>
@@ -147,7 +226,9 @@ export function SettingsView({ onImport }: Props) {
         </h3>
         <p className="mb-3 text-xs text-gray-500">
           Replace all services with configuration from a JSON file. Running
-          services will be stopped.
+          services will be stopped. Services that run a process or read TXT
+          values from a command or file are imported disabled, so review them
+          before starting them.
         </p>
         <input
           ref={fileInputRef}
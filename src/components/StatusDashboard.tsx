@@ -8,6 +8,7 @@ export function StatusDashboard({ services }: Props) {
   const running = services.filter((s) => s.status === "running").length;
   const stopped = services.filter((s) => s.status === "stopped").length;
   const error = services.filter((s) => s.status === "error").length;
+  const unhealthy = services.filter((s) => s.status === "unhealthy").length;
 
   const cards = [
     {
@@ -15,6 +16,11 @@ export function StatusDashboard({ services }: Props) {
       count: running,
       color: "bg-green-50 border-green-200 text-green-700",
     },
+    {
+      label: "Unhealthy",
+      count: unhealthy,
+      color: "bg-yellow-50 border-yellow-200 text-yellow-700",
+    },
     {
       label: "Stopped",
       count: stopped,
@@ -28,7 +34,7 @@ export function StatusDashboard({ services }: Props) {
   ];
 
   return (
-    <div className="grid grid-cols-3 gap-4">
+    <div className="grid grid-cols-4 gap-4">
       {cards.map((card) => (
         <div key={card.label} className={`rounded-lg border p-4 ${card.color}`}>
           <div className="text-2xl font-bold">{card.count}</div>
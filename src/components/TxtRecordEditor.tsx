@@ -1,28 +1,34 @@
 import { useState } from "react";
+import type { TxtEntry } from "../types";
 
 interface Props {
-  records: Record<string, string>;
-  onChange: (records: Record<string, string>) => void;
+  records: TxtEntry[];
+  onChange: (records: TxtEntry[]) => void;
+}
+
+function formatValue(entry: TxtEntry): string {
+  if (entry.value === undefined) return "(key only)";
+  if (entry.binary) return `<base64:${entry.value}>`;
+  return entry.value;
 }
 
 export function TxtRecordEditor({ records, onChange }: Props) {
   const [newKey, setNewKey] = useState("");
   const [newValue, setNewValue] = useState("");
-
-  const entries = Object.entries(records);
+  const [keyOnly, setKeyOnly] = useState(false);
 
   const addRecord = () => {
     const key = newKey.trim();
     if (!key) return;
-    onChange({ ...records, [key]: newValue });
+    const entry: TxtEntry = keyOnly ? { key } : { key, value: newValue };
+    onChange([...records.filter((r) => r.key !== key), entry]);
     setNewKey("");
     setNewValue("");
+    setKeyOnly(false);
   };
 
   const removeRecord = (key: string) => {
-    const next = { ...records };
-    delete next[key];
-    onChange(next);
+    onChange(records.filter((r) => r.key !== key));
   };
 
   return (
@@ -30,20 +36,20 @@ export function TxtRecordEditor({ records, onChange }: Props) {
       <label className="block text-sm font-medium text-gray-700">
         TXT Records
       </label>
-      {entries.length > 0 && (
+      {records.length > 0 && (
         <div className="space-y-1">
-          {entries.map(([key, value]) => (
-            <div key={key} className="flex items-center gap-2 text-sm">
+          {records.map((entry) => (
+            <div key={entry.key} className="flex items-center gap-2 text-sm">
               <span className="font-mono bg-gray-100 px-2 py-0.5 rounded">
-                {key}
+                {entry.key}
               </span>
               <span className="text-gray-400">=</span>
               <span className="font-mono bg-gray-100 px-2 py-0.5 rounded flex-1 truncate">
-                {value}
+                {formatValue(entry)}
               </span>
               <button
                 type="button"
-                onClick={() => removeRecord(key)}
+                onClick={() => removeRecord(entry.key)}
                 className="text-red-500 hover:text-red-700 text-xs"
               >
                 Remove
@@ -64,9 +70,18 @@ export function TxtRecordEditor({ records, onChange }: Props) {
           type="text"
           placeholder="Value"
           value={newValue}
+          disabled={keyOnly}
           onChange={(e) => setNewValue(e.target.value)}
-          className="flex-1 rounded border border-gray-300 px-2 py-1 text-sm"
+          className="flex-1 rounded border border-gray-300 px-2 py-1 text-sm disabled:bg-gray-100"
         />
+        <label className="flex items-center gap-1 text-xs text-gray-600">
+          <input
+            type="checkbox"
+            checked={keyOnly}
+            onChange={(e) => setKeyOnly(e.target.checked)}
+          />
+          Key only
+        </label>
         <button
           type="button"
           onClick={addRecord}
@@ -0,0 +1,86 @@
+import { useState } from "react";
+import { useWatchlist } from "../hooks/useWatchlist";
+import type { WatchEvent } from "../types";
+
+function describe(event: WatchEvent): string {
+  switch (event.change) {
+    case "appeared":
+      return `${event.fullname} appeared`;
+    case "disappeared":
+      return `${event.fullname} disappeared`;
+    case "changed":
+      return `${event.fullname} changed: ${event.details}`;
+  }
+}
+
+const changeColors: Record<WatchEvent["change"], string> = {
+  appeared: "text-green-700",
+  disappeared: "text-red-600",
+  changed: "text-orange-700",
+};
+
+export function WatchlistPanel() {
+  const { watchlist, events, error, addWatch, deleteWatch } = useWatchlist();
+  const [target, setTarget] = useState("");
+
+  const handleAdd = async () => {
+    if (!target.trim()) return;
+    await addWatch(target.trim());
+    setTarget("");
+  };
+
+  return (
+    <div>
+      <h3 className="mb-3 text-sm font-semibold text-gray-700">Watchlist</h3>
+      {error && <div className="mb-2 text-xs text-red-600">{error}</div>}
+      <div className="mb-2 flex gap-2">
+        <input
+          type="text"
+          value={target}
+          onChange={(e) => setTarget(e.target.value)}
+          placeholder="Build Cache._http._tcp.local. or _ipp._tcp"
+          className="flex-1 rounded border border-gray-300 px-2 py-1 font-mono text-sm"
+        />
+        <button
+          onClick={handleAdd}
+          className="rounded bg-gray-200 px-3 py-1 text-sm hover:bg-gray-300"
+        >
+          Watch
+        </button>
+      </div>
+      {watchlist.length > 0 && (
+        <ul className="mb-3 space-y-1">
+          {watchlist.map((entry) => (
+            <li
+              key={entry.id}
+              className="flex items-center justify-between rounded border border-gray-200 bg-white px-3 py-1 text-sm"
+            >
+              <span className="font-mono">{entry.target}</span>
+              <button
+                onClick={() => deleteWatch(entry.id)}
+                className="text-xs text-red-500 hover:text-red-700"
+              >
+                Remove
+              </button>
+            </li>
+          ))}
+        </ul>
+      )}
+      {events.length > 0 && (
+        <ul className="max-h-40 space-y-1 overflow-y-auto rounded-lg border border-gray-200 bg-white p-3 font-mono text-xs">
+          {[...events].reverse().map((event, i) => (
+            <li
+              key={`${event.timestamp}-${i}`}
+              className={changeColors[event.change]}
+            >
+              <span className="mr-2 text-gray-400">
+                {new Date(event.timestamp).toLocaleTimeString()}
+              </span>
+              {describe(event)}
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+}
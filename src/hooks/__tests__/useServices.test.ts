@@ -11,8 +11,9 @@ const mockService: ServiceView = {
   name: "Test Service",
   type: "_http._tcp",
   port: 8080,
-  txt: {},
+  txt: [],
   enabled: true,
+  conflict_policy: "report",
   status: "running",
 };
 
@@ -102,6 +103,26 @@ describe("useServices", () => {
     expect(result.current.services).toEqual([toggled]);
   });
 
+  it("startServiceFor passes the time limit", async () => {
+    mockInvoke.mockResolvedValue([]);
+
+    const { result } = renderHook(() => useServices());
+    await waitFor(() => expect(result.current.loading).toBe(false));
+
+    const limited = { ...mockService, stops_at: "2026-10-19T10:30:00Z" };
+    mockInvoke.mockResolvedValue([limited]);
+
+    await act(async () => {
+      await result.current.startServiceFor("uuid-1", 30);
+    });
+
+    expect(mockInvoke).toHaveBeenCalledWith("start_service_for", {
+      id: "uuid-1",
+      minutes: 30,
+    });
+    expect(result.current.services).toEqual([limited]);
+  });
+
   it("startAll updates state", async () => {
     const stopped = { ...mockService, status: "stopped" as const };
     mockInvoke.mockResolvedValue([stopped]);
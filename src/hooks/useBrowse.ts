@@ -0,0 +1,101 @@
+import { useCallback, useEffect, useRef, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import type {
+  BrowseEvent,
+  DiscoveredService,
+  ServiceTypeSummary,
+} from "../types";
+import * as commands from "../lib/commands";
+
+export function useBrowse() {
+  const [browsing, setBrowsing] = useState<string | null>(null);
+  const [results, setResults] = useState<DiscoveredService[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [types, setTypes] = useState<ServiceTypeSummary[]>([]);
+  const browsingRef = useRef<string | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<BrowseEvent>("browse-event", (event) => {
+      const payload = event.payload;
+      switch (payload.kind) {
+        case "resolved":
+          if (payload.service.type !== browsingRef.current) return;
+          setResults((prev) => [
+            ...prev.filter((s) => s.fullname !== payload.service.fullname),
+            payload.service,
+          ]);
+          break;
+        case "removed":
+          setResults((prev) =>
+            prev.filter((s) => s.fullname !== payload.fullname),
+          );
+          break;
+        case "stopped":
+          if (payload.type === browsingRef.current) {
+            browsingRef.current = null;
+            setBrowsing(null);
+          }
+          break;
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    commands.getServiceTypes().then(setTypes).catch(console.error);
+    const unlisten = listen<ServiceTypeSummary[]>(
+      "service-types-changed",
+      (event) => setTypes(event.payload),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Stop browsing and enumerating when the tab is closed
+  useEffect(() => {
+    return () => {
+      if (browsingRef.current) {
+        commands.stopBrowse(browsingRef.current).catch(console.error);
+      }
+      commands.stopServiceTypes().catch(console.error);
+    };
+  }, []);
+
+  const start = useCallback(async (serviceType: string) => {
+    try {
+      setError(null);
+      if (browsingRef.current) {
+        await commands.stopBrowse(browsingRef.current);
+      }
+      setResults([]);
+      const type = await commands.startBrowse(serviceType);
+      browsingRef.current = type;
+      setBrowsing(type);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, []);
+
+  const stop = useCallback(async () => {
+    if (!browsingRef.current) return;
+    try {
+      await commands.stopBrowse(browsingRef.current);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, []);
+
+  const clone = useCallback(async (fullname: string, asProxy: boolean) => {
+    try {
+      setError(null);
+      await commands.cloneDiscoveredService(fullname, asProxy);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, []);
+
+  return { browsing, results, types, error, start, stop, clone };
+}
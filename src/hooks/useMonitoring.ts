@@ -1,12 +1,20 @@
 import { useCallback, useEffect, useState } from "react";
 import { listen } from "@tauri-apps/api/event";
-import type { LogEntry, LogLevel, NetworkInterface } from "../types";
+import type {
+  DaemonEventEntry,
+  LogEntry,
+  LogLevel,
+  NetworkInterface,
+} from "../types";
 import * as commands from "../lib/commands";
 
+const MAX_DAEMON_EVENTS = 100;
+
 export function useMonitoring() {
   const [logs, setLogs] = useState<LogEntry[]>([]);
   const [interfaces, setInterfaces] = useState<NetworkInterface[]>([]);
   const [levelFilter, setLevelFilter] = useState<LogLevel | "all">("all");
+  const [daemonEvents, setDaemonEvents] = useState<DaemonEventEntry[]>([]);
 
   useEffect(() => {
     commands.getEventLogs().then(setLogs).catch(console.error);
@@ -22,6 +30,19 @@ export function useMonitoring() {
     };
   }, []);
 
+  useEffect(() => {
+    const unlisten = listen<DaemonEventEntry>("daemon-event", (event) => {
+      const entry = event.payload;
+      setDaemonEvents((prev) => [...prev, entry].slice(-MAX_DAEMON_EVENTS));
+      if (entry.kind === "ip_added" || entry.kind === "ip_removed") {
+        commands.getNetworkInterfaces().then(setInterfaces).catch(console.error);
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   const clearLogs = useCallback(async () => {
     await commands.clearEventLogs();
     setLogs([]);
@@ -39,6 +60,7 @@ export function useMonitoring() {
     logs: filteredLogs,
     allLogs: logs,
     interfaces,
+    daemonEvents,
     levelFilter,
     setLevelFilter,
     clearLogs,
@@ -1,6 +1,6 @@
 import { useCallback, useEffect, useState } from "react";
 import { listen } from "@tauri-apps/api/event";
-import type { ServiceView } from "../types";
+import type { ServiceView, TxtEntry } from "../types";
 import * as commands from "../lib/commands";
 
 export function useServices() {
@@ -38,7 +38,7 @@ export function useServices() {
       name: string,
       serviceType: string,
       port: number,
-      txt: Record<string, string>,
+      txt: TxtEntry[],
       enabled: boolean,
     ) => {
       try {
@@ -64,7 +64,7 @@ export function useServices() {
       name: string,
       serviceType: string,
       port: number,
-      txt: Record<string, string>,
+      txt: TxtEntry[],
       enabled: boolean,
     ) => {
       try {
@@ -105,6 +105,16 @@ export function useServices() {
     }
   }, []);
 
+  const startServiceFor = useCallback(async (id: string, minutes: number) => {
+    try {
+      const result = await commands.startServiceFor(id, minutes);
+      setServices(result);
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    }
+  }, []);
+
   const startAll = useCallback(async () => {
     try {
       const result = await commands.startAll();
@@ -144,6 +154,7 @@ export function useServices() {
     updateService,
     deleteService,
     toggleService,
+    startServiceFor,
     startAll,
     stopAll,
     importConfig,
@@ -0,0 +1,46 @@
+import { useCallback, useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import type { WatchEntry, WatchEvent } from "../types";
+import * as commands from "../lib/commands";
+
+const MAX_WATCH_EVENTS = 50;
+
+export function useWatchlist() {
+  const [watchlist, setWatchlist] = useState<WatchEntry[]>([]);
+  const [events, setEvents] = useState<WatchEvent[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    commands.getWatchlist().then(setWatchlist).catch(console.error);
+    const unlistenList = listen<WatchEntry[]>("watchlist-changed", (event) =>
+      setWatchlist(event.payload),
+    );
+    const unlistenEvents = listen<WatchEvent>("watch-event", (event) =>
+      setEvents((prev) => [...prev, event.payload].slice(-MAX_WATCH_EVENTS)),
+    );
+    return () => {
+      unlistenList.then((fn) => fn());
+      unlistenEvents.then((fn) => fn());
+    };
+  }, []);
+
+  const addWatch = useCallback(async (target: string) => {
+    try {
+      setError(null);
+      setWatchlist(await commands.addWatch(target));
+    } catch (e) {
+      setError(String(e));
+    }
+  }, []);
+
+  const deleteWatch = useCallback(async (id: string) => {
+    try {
+      setError(null);
+      setWatchlist(await commands.deleteWatch(id));
+    } catch (e) {
+      setError(String(e));
+    }
+  }, []);
+
+  return { watchlist, events, error, addWatch, deleteWatch };
+}
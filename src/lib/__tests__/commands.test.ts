@@ -9,6 +9,7 @@ import {
   startAll,
   stopAll,
   getHostName,
+  setHostnameOverride,
   getEventLogs,
   clearEventLogs,
   getNetworkInterfaces,
@@ -32,26 +33,36 @@ describe("commands", () => {
 
   it("addService calls invoke with correct args", async () => {
     mockInvoke.mockResolvedValue([]);
-    await addService("test", "_http._tcp", 8080, { key: "val" }, true);
+    await addService(
+      "test",
+      "_http._tcp",
+      8080,
+      [{ key: "key", value: "val" }],
+      true,
+    );
     expect(mockInvoke).toHaveBeenCalledWith("add_service", {
-      name: "test",
-      serviceType: "_http._tcp",
-      port: 8080,
-      txt: { key: "val" },
-      enabled: true,
+      service: {
+        name: "test",
+        type: "_http._tcp",
+        port: 8080,
+        txt: [{ key: "key", value: "val" }],
+        enabled: true,
+      },
     });
   });
 
   it("updateService calls invoke with correct args", async () => {
     mockInvoke.mockResolvedValue([]);
-    await updateService("id-1", "test", "_http._tcp", 8080, {}, false);
+    await updateService("id-1", "test", "_http._tcp", 8080, [], false);
     expect(mockInvoke).toHaveBeenCalledWith("update_service", {
       id: "id-1",
-      name: "test",
-      serviceType: "_http._tcp",
-      port: 8080,
-      txt: {},
-      enabled: false,
+      changes: {
+        name: "test",
+        type: "_http._tcp",
+        port: 8080,
+        txt: [],
+        enabled: false,
+      },
     });
   });
 
@@ -86,6 +97,14 @@ describe("commands", () => {
     expect(result).toBe("my-host");
   });
 
+  it("setHostnameOverride passes null to clear the override", async () => {
+    mockInvoke.mockResolvedValue({ effective: "my-host", os: "my-host" });
+    await setHostnameOverride(null);
+    expect(mockInvoke).toHaveBeenCalledWith("set_hostname_override", {
+      hostname: null,
+    });
+  });
+
   it("getEventLogs calls invoke with correct command", async () => {
     mockInvoke.mockResolvedValue([]);
     await getEventLogs();
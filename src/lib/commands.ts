@@ -1,5 +1,14 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { LogEntry, NetworkInterface, ServiceView } from "../types";
+import type {
+  HostInfo,
+  LogEntry,
+  NetworkInterface,
+  ServiceInput,
+  ServiceTypeSummary,
+  ServiceView,
+  TxtEntry,
+  WatchEntry,
+} from "../types";
 
 export async function getServices(): Promise<ServiceView[]> {
   return invoke("get_services");
@@ -9,16 +18,17 @@ export async function addService(
   name: string,
   serviceType: string,
   port: number,
-  txt: Record<string, string>,
+  txt: TxtEntry[],
   enabled: boolean,
 ): Promise<ServiceView[]> {
-  return invoke("add_service", {
+  const service: ServiceInput = {
     name,
-    serviceType,
+    type: serviceType,
     port,
     txt,
     enabled,
-  });
+  };
+  return invoke("add_service", { service });
 }
 
 export async function updateService(
@@ -26,17 +36,18 @@ export async function updateService(
   name: string,
   serviceType: string,
   port: number,
-  txt: Record<string, string>,
+  txt: TxtEntry[],
   enabled: boolean,
 ): Promise<ServiceView[]> {
-  return invoke("update_service", {
-    id,
+  // Settings the form does not edit are left out so they are kept
+  const changes: ServiceInput = {
     name,
-    serviceType,
+    type: serviceType,
     port,
     txt,
     enabled,
-  });
+  };
+  return invoke("update_service", { id, changes });
 }
 
 export async function deleteService(id: string): Promise<ServiceView[]> {
@@ -47,6 +58,13 @@ export async function toggleService(id: string): Promise<ServiceView[]> {
   return invoke("toggle_service", { id });
 }
 
+export async function startServiceFor(
+  id: string,
+  minutes: number,
+): Promise<ServiceView[]> {
+  return invoke("start_service_for", { id, minutes });
+}
+
 export async function startAll(): Promise<ServiceView[]> {
   return invoke("start_all");
 }
@@ -59,6 +77,16 @@ export async function getHostName(): Promise<string> {
   return invoke("get_host_name");
 }
 
+export async function getHostInfo(): Promise<HostInfo> {
+  return invoke("get_host_info");
+}
+
+export async function setHostnameOverride(
+  hostname: string | null,
+): Promise<HostInfo> {
+  return invoke("set_hostname_override", { hostname });
+}
+
 export async function getEventLogs(): Promise<LogEntry[]> {
   return invoke("get_event_logs");
 }
@@ -71,6 +99,42 @@ export async function getNetworkInterfaces(): Promise<NetworkInterface[]> {
   return invoke("get_network_interfaces");
 }
 
+export async function startBrowse(serviceType: string): Promise<string> {
+  return invoke("start_browse", { serviceType });
+}
+
+export async function stopBrowse(serviceType: string): Promise<void> {
+  return invoke("stop_browse", { serviceType });
+}
+
+export async function getServiceTypes(): Promise<ServiceTypeSummary[]> {
+  return invoke("get_service_types");
+}
+
+export async function stopServiceTypes(): Promise<void> {
+  return invoke("stop_service_types");
+}
+
+export async function cloneDiscoveredService(
+  fullname: string,
+  asProxy: boolean,
+  name?: string,
+): Promise<ServiceView[]> {
+  return invoke("clone_discovered_service", { fullname, asProxy, name });
+}
+
+export async function getWatchlist(): Promise<WatchEntry[]> {
+  return invoke("get_watchlist");
+}
+
+export async function addWatch(target: string): Promise<WatchEntry[]> {
+  return invoke("add_watch", { target });
+}
+
+export async function deleteWatch(id: string): Promise<WatchEntry[]> {
+  return invoke("delete_watch", { id });
+}
+
 export async function exportConfig(): Promise<string> {
   return invoke("export_config");
 }
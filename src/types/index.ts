@@ -1,18 +1,153 @@
+export type ServiceStatus =
+  | "running"
+  | "stopped"
+  | "error"
+  | "conflict"
+  | "unhealthy"
+  | "waiting"
+  | "scheduled"
+  | "retrying";
+
+export type ConflictPolicy = "report" | "number" | "hostname";
+
+export interface TxtEntry {
+  key: string;
+  /** Omitted for key-only boolean attributes. */
+  value?: string;
+  /** `value` is base64-encoded binary data. */
+  binary?: boolean;
+}
+
+export type TxtSource = (
+  | { source: "command"; command: string }
+  | { source: "file"; path: string }
+) & {
+  key: string;
+  interval_secs: number;
+};
+
 export interface ServiceView {
   id: string;
   name: string;
   type: string;
   port: number;
-  txt: Record<string, string>;
+  txt: TxtEntry[];
+  txt_sources?: TxtSource[];
   enabled: boolean;
-  status: "running" | "stopped" | "error";
+  interfaces?: InterfaceFilter;
+  proxy?: ProxyTarget;
+  hostname?: string;
+  subtype?: string;
+  conflict_policy: ConflictPolicy;
+  health_check?: HealthCheck;
+  process?: ManagedProcess;
+  bound_process?: ProcessBinding;
+  schedule?: Schedule;
+  retry?: RetryPolicy;
+  /** When a time-limited start ends. */
+  stops_at?: string;
+  /** When the schedule next opens or closes. */
+  next_transition?: string;
+  /** PID of the managed process while it runs. */
+  pid?: number;
+  /** `name` with its templates expanded, while published and different. */
+  rendered_name?: string;
+  /** `txt` with templates expanded and sourced values merged in, while
+   * published and different. */
+  rendered_txt?: TxtEntry[];
+  published_name?: string;
+  /** When the current advertisement was last announced. */
+  published_at?: string;
+  /** Failed registrations so far, while retrying. */
+  retry_attempt?: number;
+  /** When the next registration attempt is due, while retrying. */
+  next_retry?: string;
+  status: ServiceStatus;
+  verification?: Verification;
+}
+
+/** A service to add, or the changes to one. Fields left out keep their
+ * current value on an update; `null` removes an optional setting. */
+export interface ServiceInput {
+  name?: string;
+  type?: string;
+  port?: number;
+  txt?: TxtEntry[];
+  txt_sources?: TxtSource[];
+  enabled?: boolean;
+  interfaces?: InterfaceFilter | null;
+  proxy?: ProxyTarget | null;
+  hostname?: string | null;
+  subtype?: string | null;
+  conflict_policy?: ConflictPolicy;
+  health_check?: HealthCheck | null;
+  process?: ManagedProcess | null;
+  bound_process?: ProcessBinding | null;
+  schedule?: Schedule | null;
+  retry?: RetryPolicy | null;
+}
+
+export type Verification =
+  | { state: "pending" }
+  | { state: "verified" }
+  | { state: "unverified"; reason: string };
+
+export type HealthCheck = (
+  | { probe: "tcp" }
+  | { probe: "http"; path: string; expected_status: number }
+) & {
+  interval_secs: number;
+  failure_threshold: number;
+  success_threshold: number;
+};
+
+export type RestartPolicy = "never" | "on-failure" | "always";
+
+export interface ManagedProcess {
+  command: string;
+  args?: string[];
+  cwd?: string;
+  env?: Record<string, string>;
+  restart: RestartPolicy;
+}
+
+export type ProcessBinding =
+  | { by: "pid"; pid: number }
+  | { by: "pidfile"; path: string }
+  | { by: "name"; pattern: string };
+
+export type Weekday = "Mon" | "Tue" | "Wed" | "Thu" | "Fri" | "Sat" | "Sun";
+
+export interface Schedule {
+  /** Every day when omitted. */
+  days?: Weekday[];
+  /** `HH:MM` local time. */
+  start: string;
+  end: string;
+}
+
+export interface RetryPolicy {
+  /** Unlimited when omitted. */
+  max_attempts?: number;
+  initial_backoff_secs: number;
+  max_backoff_secs: number;
+}
+
+export interface ProxyTarget {
+  host: string;
+  addresses: string[];
+}
+
+export interface InterfaceFilter {
+  mode: "allow" | "deny";
+  patterns: string[];
 }
 
 export interface AddServiceParams {
   name: string;
   serviceType: string;
   port: number;
-  txt: Record<string, string>;
+  txt: TxtEntry[];
   enabled: boolean;
 }
 
@@ -20,6 +155,53 @@ export interface UpdateServiceParams extends AddServiceParams {
   id: string;
 }
 
+export interface DiscoveredService {
+  fullname: string;
+  name: string;
+  type: string;
+  host: string;
+  port: number;
+  addresses: string[];
+  txt: TxtEntry[];
+  /** Published by this app. */
+  own: boolean;
+}
+
+export type BrowseEvent =
+  | { kind: "resolved"; service: DiscoveredService }
+  | { kind: "removed"; type: string; fullname: string }
+  | { kind: "stopped"; type: string };
+
+export interface ServiceTypeSummary {
+  type: string;
+  instances: number;
+  first_seen: string;
+  last_seen: string;
+}
+
+export interface WatchEntry {
+  id: string;
+  /** Instance fullname or service type. */
+  target: string;
+}
+
+export type WatchChange =
+  | { change: "appeared" }
+  | { change: "disappeared" }
+  | { change: "changed"; details: string };
+
+export type WatchEvent = {
+  timestamp: string;
+  watch_id: string;
+  fullname: string;
+} & WatchChange;
+
+export interface HostInfo {
+  effective: string;
+  os: string;
+  hostname_override?: string;
+}
+
 export type LogLevel = "info" | "warn" | "error";
 
 export interface LogEntry {
@@ -29,6 +211,15 @@ export interface LogEntry {
   service_id?: string;
 }
 
+export type DaemonEventKind =
+  | { kind: "error"; message: string }
+  | { kind: "ip_added"; address: string }
+  | { kind: "ip_removed"; address: string }
+  | { kind: "name_changed"; original: string; new_name: string }
+  | { kind: "announced"; fullname: string; interface: string };
+
+export type DaemonEventEntry = { timestamp: string } & DaemonEventKind;
+
 export interface NetworkInterface {
   name: string;
   addresses: string[];